@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use aoc_common::{input_lines, Answer, ParseError, Solver};
+
+pub fn parse_location_lists(data: &[u8]) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
+    let mut left_list = Vec::new();
+    let mut right_list = Vec::new();
+    for line in input_lines(data)? {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        let mut numbers = line.text.split_whitespace();
+        let mut next_number = || match numbers.next() {
+            Some(num) => line.parse::<i32>(num, "a location ID"),
+            None => Err(line.expected("", "two location IDs")),
+        };
+        left_list.push(next_number()?);
+        right_list.push(next_number()?);
+        if let Some(extra) = numbers.next() {
+            return Err(line.expected(extra, "the end of the line"));
+        }
+    }
+    Ok((left_list, right_list))
+}
+
+pub fn part1(mut left_list: Vec<i32>, mut right_list: Vec<i32>) -> u32 {
+    left_list.sort();
+    right_list.sort();
+    let mut sum = 0;
+    for (left, right) in left_list.iter().zip(right_list.iter()) {
+        sum += left.abs_diff(*right);
+    }
+    sum
+}
+
+pub fn part2_dumb(left_list: Vec<i32>, right_list: Vec<i32>) -> u32 {
+    let mut similarity = 0;
+    for left in &left_list {
+        let mut multiplicator = 0;
+        for right in &right_list {
+            if left == right {
+                multiplicator += 1;
+            }
+        }
+        similarity += left * multiplicator;
+    }
+    similarity as u32
+}
+
+pub fn part2_smart(left_list: Vec<i32>, right_list: Vec<i32>) -> u32 {
+    // Count occurrences of each number in right_list
+    let mut right_counts = HashMap::new();
+    for &num in &right_list {
+        *right_counts.entry(num).or_insert(0) += 1;
+    }
+
+    // Calculate similarity
+    let similarity: i32 = left_list
+        .iter()
+        .map(|&left| left * right_counts.get(&left).unwrap_or(&0))
+        .sum();
+
+    similarity as u32
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = (Vec<i32>, Vec<i32>);
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_location_lists(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input.0.clone(), input.1.clone()).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2_smart(input.0.clone(), input.1.clone()).into()
+    }
+}
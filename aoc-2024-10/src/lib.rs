@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+
+use aoc_common::{Answer, ParseError, Solver};
+use aoc_grid::{Coord2D, Grid};
+
+const DEBUG: bool = false;
+
+pub struct Topo {
+    pub m: Grid<u8>,
+    pub trailheads: Vec<(usize, usize)>,
+}
+
+pub struct TrailContext {
+    pub reached_tops: Option<HashSet<(usize, usize)>>,
+    pub score: u32,
+}
+
+impl Default for TrailContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TrailContext {
+    pub fn new() -> Self {
+        Self {
+            reached_tops: Some(HashSet::new()),
+            score: 0,
+        }
+    }
+
+    pub fn new_for_part2() -> Self {
+        Self {
+            reached_tops: None,
+            score: 0,
+        }
+    }
+}
+
+impl Topo {
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        let m = Grid::parse_with(data, "topographic map", |_, glyph| {
+            glyph.is_ascii_digit().then(|| glyph - b'0')
+        })?;
+        let trailheads = m
+            .iter()
+            .filter(|(_, &height)| height == 0)
+            .map(|(pos, _)| pos.into())
+            .collect();
+        Ok(Self { m, trailheads })
+    }
+
+    /// Returns the sum of the trailhead scores for part 1 and part 2.
+    pub fn find_trails(&self) -> (u32, u32) {
+        let path = Vec::with_capacity(9);
+        let mut score_sum_p1 = 0;
+        let mut score_sum_p2 = 0;
+        for (x, y) in &self.trailheads {
+            if DEBUG {
+                println!("Checking trailhead at {}, {}", x, y);
+            }
+            let mut ctx = TrailContext::new();
+            self.check_trail(path.clone(), *x, *y, &mut ctx);
+            if DEBUG {
+                println!("Trail had a score of {} for p1", ctx.score);
+            }
+            score_sum_p1 += ctx.score;
+            let mut ctx = TrailContext::new_for_part2();
+            self.check_trail(path.clone(), *x, *y, &mut ctx);
+            score_sum_p2 += ctx.score;
+            if DEBUG {
+                println!("Trail had a score of {} for p2", ctx.score);
+            }
+        }
+        (score_sum_p1, score_sum_p2)
+    }
+
+    pub fn check_trail(
+        &self,
+        mut path: Vec<(usize, usize)>,
+        x: usize,
+        y: usize,
+        ctx: &mut TrailContext,
+    ) {
+        let cur_height = self.m[(x, y)];
+        // Trail is complete, increase the score of the trailhead.
+        if cur_height == 9 {
+            if let Some(reached_tops) = &mut ctx.reached_tops {
+                if !reached_tops.insert((x, y)) {
+                    return;
+                }
+            }
+            path.push((x, y));
+            ctx.score += 1;
+            if DEBUG {
+                println!("completed trail with path {:?}", path);
+            }
+            return;
+        }
+        path.push((x, y));
+        for next in self.m.neighbors4(Coord2D::new(x, y)) {
+            if self.m[next] == cur_height + 1 {
+                self.check_trail(path.clone(), next.x, next.y, ctx);
+            }
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Topo;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        Topo::new(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.find_trails().0.into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.find_trails().1.into()
+    }
+}
@@ -0,0 +1,385 @@
+//! Blinking by stone value. Stones with the same engraving turn into the same stones and the order
+//! of the row never changes a count, so a generation is just the number of stones per value. The
+//! values a row ever shows form a small closed set (a few thousand), so every value is interned
+//! once together with the values it turns into, and a blink is one pass over a dense count vector.
+//!
+//! The number of stones grows by about half per blink: `u64` holds the 75 blinks of part two,
+//! `u128` lasts for about 190 blinks and [`BigCount`] is exact for any number of blinks. A
+//! [`BigCount`] add costs one step per 64 bit limb and the limbs grow with the number of blinks, so
+//! exact counting takes quadratic time: 1000 blinks take about a tenth of a second, 5000 about a
+//! second.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::iter;
+
+use aoc_common::Answer;
+
+use crate::{apply_blink_algo, BlinkResult};
+
+/// A number of stones.
+pub trait Count: Clone + Default + Display {
+    fn one() -> Self;
+    fn is_zero(&self) -> bool;
+    /// Adds `other`, returning false if the sum does not fit.
+    fn try_add(&mut self, other: &Self) -> bool;
+    /// Sets the count back to zero, keeping any allocation around for reuse.
+    fn set_zero(&mut self) {
+        *self = Self::default();
+    }
+}
+
+macro_rules! primitive_count {
+    ($($ty:ty),*) => {$(
+        impl Count for $ty {
+            fn one() -> Self {
+                1
+            }
+
+            fn is_zero(&self) -> bool {
+                *self == 0
+            }
+
+            fn try_add(&mut self, other: &Self) -> bool {
+                match self.checked_add(*other) {
+                    Some(sum) => {
+                        *self = sum;
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+    )*};
+}
+
+primitive_count!(u64, u128);
+
+/// An unsigned integer of any size, as little endian 64 bit limbs without trailing zero limbs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BigCount(Vec<u64>);
+
+impl Count for BigCount {
+    fn one() -> Self {
+        Self(vec![1])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn try_add(&mut self, other: &Self) -> bool {
+        if self.0.len() < other.0.len() {
+            self.0.resize(other.0.len(), 0);
+        }
+        let (low, high) = self.0.split_at_mut(other.0.len());
+        let mut carry = false;
+        for (limb, &addend) in low.iter_mut().zip(&other.0) {
+            let (sum, first) = limb.overflowing_add(addend);
+            let (sum, second) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = first | second;
+        }
+        for limb in high {
+            if !carry {
+                return true;
+            }
+            let (sum, overflow) = limb.overflowing_add(1);
+            *limb = sum;
+            carry = overflow;
+        }
+        if carry {
+            self.0.push(1);
+        }
+        true
+    }
+
+    fn set_zero(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl From<u128> for BigCount {
+    fn from(value: u128) -> Self {
+        let mut limbs = vec![value as u64, (value >> 64) as u64];
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self(limbs)
+    }
+}
+
+impl Display for BigCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut limbs = self.0.clone();
+        // Groups of 19 decimal digits, least significant first.
+        let mut chunks = Vec::new();
+        while !limbs.is_empty() {
+            let mut remainder = 0u128;
+            for limb in limbs.iter_mut().rev() {
+                let current = (remainder << 64) | *limb as u128;
+                *limb = (current / CHUNK as u128) as u64;
+                remainder = current % CHUNK as u128;
+            }
+            chunks.push(remainder as u64);
+            while limbs.last() == Some(&0) {
+                limbs.pop();
+            }
+        }
+        match chunks.split_last() {
+            None => f.pad("0"),
+            Some((first, rest)) => {
+                let mut digits = first.to_string();
+                for chunk in rest.iter().rev() {
+                    digits.push_str(&format!("{:019}", chunk));
+                }
+                f.pad(&digits)
+            }
+        }
+    }
+}
+
+/// The count type can not hold the stones of a generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub generation: usize,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the number of stones after {} blinks does not fit the count type",
+            self.generation
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// The stones of one generation, counted per engraved value.
+#[derive(Debug, Clone)]
+pub struct StoneCounts<C> {
+    values: Vec<u64>,
+    index: HashMap<u64, usize>,
+    /// The values each value turns into, once it has been blinked at.
+    children: Vec<Option<(usize, Option<usize>)>>,
+    counts: Vec<C>,
+    /// Scratch counts for the next generation, all zero between blinks.
+    next: Vec<C>,
+    generation: usize,
+}
+
+impl<C: Count> StoneCounts<C> {
+    pub fn new(stones: &[u64]) -> Result<Self, Overflow> {
+        let mut counts = Self {
+            values: Vec::new(),
+            index: HashMap::new(),
+            children: Vec::new(),
+            counts: Vec::new(),
+            next: Vec::new(),
+            generation: 0,
+        };
+        for &stone in stones {
+            let idx = counts.intern(stone);
+            counts.counts.resize(counts.values.len(), C::default());
+            if !counts.counts[idx].try_add(&C::one()) {
+                return Err(Overflow { generation: 0 });
+            }
+        }
+        Ok(counts)
+    }
+
+    fn intern(&mut self, value: u64) -> usize {
+        *self.index.entry(value).or_insert_with(|| {
+            self.values.push(value);
+            self.children.push(None);
+            self.values.len() - 1
+        })
+    }
+
+    fn children(&mut self, idx: usize) -> (usize, Option<usize>) {
+        if let Some(children) = self.children[idx] {
+            return children;
+        }
+        let children = match apply_blink_algo(self.values[idx]) {
+            BlinkResult::Replaced(value) => (self.intern(value), None),
+            BlinkResult::Split(first, second) => (self.intern(first), Some(self.intern(second))),
+        };
+        self.children[idx] = Some(children);
+        children
+    }
+
+    /// Blinks once. On overflow the stones stay at the current generation.
+    pub fn blink(&mut self) -> Result<(), Overflow> {
+        let mut next = std::mem::take(&mut self.next);
+        let mut overflow = false;
+        'stones: for idx in 0..self.counts.len() {
+            if self.counts[idx].is_zero() {
+                continue;
+            }
+            let (first, second) = self.children(idx);
+            next.resize(self.values.len(), C::default());
+            for child in iter::once(first).chain(second) {
+                if !next[child].try_add(&self.counts[idx]) {
+                    overflow = true;
+                    break 'stones;
+                }
+            }
+        }
+        if overflow {
+            next.iter_mut().for_each(C::set_zero);
+            self.next = next;
+            return Err(Overflow {
+                generation: self.generation + 1,
+            });
+        }
+        self.counts.resize(self.values.len(), C::default());
+        next.resize(self.values.len(), C::default());
+        self.next = std::mem::replace(&mut self.counts, next);
+        self.next.iter_mut().for_each(C::set_zero);
+        self.generation += 1;
+        Ok(())
+    }
+
+    pub fn blink_n_times(&mut self, times: usize) -> Result<(), Overflow> {
+        for _ in 0..times {
+            self.blink()?;
+        }
+        Ok(())
+    }
+
+    /// The number of blinks so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn total(&self) -> Result<C, Overflow> {
+        let mut total = C::default();
+        for count in &self.counts {
+            if !total.try_add(count) {
+                return Err(Overflow {
+                    generation: self.generation,
+                });
+            }
+        }
+        Ok(total)
+    }
+
+    /// The number of different values in the current generation.
+    pub fn distinct(&self) -> usize {
+        self.counts.iter().filter(|count| !count.is_zero()).count()
+    }
+
+    /// The number of stones per value in the current generation.
+    pub fn histogram(&self) -> HashMap<u64, C> {
+        self.values
+            .iter()
+            .zip(&self.counts)
+            .filter(|(_, count)| !count.is_zero())
+            .map(|(&value, count)| (value, count.clone()))
+            .collect()
+    }
+}
+
+/// The number of stones after blinking `times` times at `stones`.
+pub fn count_stones<C: Count>(stones: &[u64], times: usize) -> Result<C, Overflow> {
+    let mut counts = StoneCounts::new(stones)?;
+    counts.blink_n_times(times)?;
+    counts.total()
+}
+
+/// The number of stones after blinking `times` times at `stones`, counted in `u64` and only in
+/// the wider count types if it does not fit.
+pub fn count_answer(stones: &[u64], times: usize) -> Answer {
+    if let Ok(count) = count_stones::<u64>(stones, times) {
+        return count.into();
+    }
+    if let Ok(count) = count_stones::<u128>(stones, times) {
+        return count.to_string().into();
+    }
+    match count_stones::<BigCount>(stones, times) {
+        Ok(count) => count.to_string().into(),
+        Err(overflow) => Answer::Failed(overflow.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let mut counts = StoneCounts::<u64>::new(&[125, 17]).unwrap();
+        counts.blink_n_times(6).unwrap();
+        assert_eq!(counts.generation(), 6);
+        assert_eq!(counts.total(), Ok(22));
+        let histogram = counts.histogram();
+        assert_eq!(histogram[&2], 4);
+        assert_eq!(histogram[&0], 2);
+        assert_eq!(histogram[&2097446912], 1);
+        assert_eq!(histogram.values().sum::<u64>(), 22);
+        assert_eq!(counts.distinct(), histogram.len());
+        assert_eq!(count_stones::<u64>(&[125, 17], 25), Ok(55312));
+    }
+
+    #[test]
+    fn matches_recursion() {
+        let stones = [0, 1, 10, 99, 999, 2024, 125, 17];
+        for times in [0, 1, 5, 20, 30] {
+            let expected: usize = stones
+                .iter()
+                .map(|&val| 1 + crate::StoneRecursor::blink_with_stack(val, times))
+                .sum();
+            assert_eq!(count_stones::<u64>(&stones, times), Ok(expected as u64));
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let mut counts = StoneCounts::<u64>::new(&[125, 17]).unwrap();
+        let error = counts.blink_n_times(200).unwrap_err();
+        let generation = counts.generation();
+        assert_eq!(generation + 1, error.generation);
+        let histogram = counts.histogram();
+        assert!(counts.blink().is_err());
+        assert_eq!(counts.generation(), generation);
+        assert_eq!(counts.histogram(), histogram);
+        // The sum overflows before any single count does.
+        assert_eq!(counts.total(), Err(Overflow { generation }));
+
+        let wide = count_stones::<u128>(&[125, 17], generation).unwrap();
+        let big = count_stones::<BigCount>(&[125, 17], generation).unwrap();
+        assert!(wide > u64::MAX as u128);
+        assert_eq!(big, BigCount::from(wide));
+        assert_eq!(big.to_string(), wide.to_string());
+        assert!(count_stones::<u128>(&[125, 17], 500).is_err());
+        assert!(count_stones::<BigCount>(&[125, 17], 500).is_ok());
+
+        assert_eq!(count_answer(&[125, 17], 25), Answer::Number(55312));
+        assert_eq!(
+            count_answer(&[125, 17], generation),
+            wide.to_string().into()
+        );
+        let big = count_stones::<BigCount>(&[125, 17], 500).unwrap();
+        assert_eq!(count_answer(&[125, 17], 500), big.to_string().into());
+    }
+
+    #[test]
+    fn big_count() {
+        let mut count = BigCount::from(u128::MAX);
+        assert_eq!(count.to_string(), u128::MAX.to_string());
+        assert!(count.try_add(&BigCount::one()));
+        assert_eq!(count, BigCount(vec![0, 0, 1]));
+        assert_eq!(count.to_string(), "340282366920938463463374607431768211456");
+        assert!(count.try_add(&count.clone()));
+        assert_eq!(count, BigCount(vec![0, 0, 2]));
+        let mut carried = BigCount(vec![u64::MAX, u64::MAX, 5]);
+        assert!(carried.try_add(&BigCount::one()));
+        assert_eq!(carried, BigCount(vec![0, 0, 6]));
+        assert_eq!(BigCount::default().to_string(), "0");
+        assert_eq!(format!("{:>3}", BigCount::one()), "  1");
+        count.set_zero();
+        assert!(count.is_zero());
+    }
+}
@@ -1,4 +1,19 @@
-use std::io::BufRead;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::default::Default;
+use std::sync::{
+    atomic::{self, AtomicUsize},
+    Arc, Mutex,
+};
+
+use aoc_common::{input_lines, Answer, ParseError, Solver};
+use count_map::count_answer;
+
+pub mod count_map;
+pub mod list_based;
+
+pub const CALC_POINT_P1: usize = 25;
+pub const CALC_POINT_P2: usize = 75;
 
 #[derive(Debug)]
 pub enum BlinkResult {
@@ -10,8 +25,8 @@ pub fn apply_blink_algo(val: u64) -> BlinkResult {
     match val {
         0 => BlinkResult::Replaced(1),
         _ => {
-            let digits = (val as f64).log10().floor() as u32 + 1; // Number of digits
-            if digits % 2 == 0 {
+            let digits = val.ilog10() + 1; // Number of digits
+            if digits.is_multiple_of(2) {
                 let divisor = 10u64.pow(digits / 2); // Power of 10 to split the number
                 BlinkResult::Split(val / divisor, val % divisor)
             } else {
@@ -21,13 +36,152 @@ pub fn apply_blink_algo(val: u64) -> BlinkResult {
     }
 }
 
-pub fn get_initial_stones(data: &[u8]) -> Vec<u64> {
+pub fn get_initial_stones(data: &[u8]) -> Result<Vec<u64>, ParseError> {
     let mut stones = Vec::new();
-    for line in data.lines() {
-        let line = line.unwrap();
-        for num in line.split_whitespace() {
-            stones.push(num.parse().unwrap());
+    for line in input_lines(data)? {
+        for num in line.text.split_whitespace() {
+            stones.push(line.parse(num, "an engraved number")?);
         }
     }
-    stones
+    Ok(stones)
+}
+
+#[derive(Debug)]
+pub struct StoneRecursor {
+    init_stones: Vec<u64>,
+    pub total_num_of_stones: Arc<AtomicUsize>,
+    cache: Arc<Mutex<HashMap<(u64, usize), usize>>>,
+}
+
+impl StoneRecursor {
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        get_initial_stones(data).map(Self::new_from_stones)
+    }
+
+    pub fn new_from_stones(init_stones: Vec<u64>) -> Self {
+        let total_num_of_stones = init_stones.len();
+
+        Self {
+            init_stones,
+            total_num_of_stones: Arc::new(AtomicUsize::new(total_num_of_stones)),
+            cache: Default::default(),
+        }
+    }
+    pub fn blink_n_times(&self, times: usize) -> usize {
+        for val in self.init_stones.clone() {
+            let mut num_of_stones = 0;
+            self.blink_recursion(val, 0, times, &mut num_of_stones);
+            self.total_num_of_stones
+                .fetch_add(num_of_stones, atomic::Ordering::Relaxed);
+        }
+        let stones = self.total_num_of_stones.load(atomic::Ordering::Relaxed);
+        self.reset();
+        stones
+    }
+
+    pub fn blink_n_times_parallelized(&self, times: usize) -> usize {
+        // Process each initial stone in parallel
+        self.init_stones.par_iter().for_each(|&val| {
+            let mut num_of_stones = 0;
+            self.blink_recursion(val, 0, times, &mut num_of_stones);
+            // Aggregate the total number of stones
+            self.total_num_of_stones
+                .fetch_add(num_of_stones, atomic::Ordering::Relaxed);
+        });
+
+        let stones = self.total_num_of_stones.load(atomic::Ordering::Relaxed);
+        self.reset();
+        stones
+    }
+
+    pub fn reset(&self) {
+        self.total_num_of_stones
+            .store(self.init_stones.len(), atomic::Ordering::Relaxed);
+        self.cache.lock().unwrap().clear();
+    }
+
+    pub fn blink_recursion(
+        &self,
+        val: u64,
+        blink_depth: usize,
+        times: usize,
+        num_of_stones: &mut usize,
+    ) {
+        // Check if result is already cached
+        {
+            let cache = self.cache.lock().unwrap();
+            if let Some(&cached_result) = cache.get(&(val, blink_depth)) {
+                *num_of_stones += cached_result;
+                return;
+            }
+        }
+
+        // Perform computation if not cached
+        let mut stones_for_this_call = 0;
+
+        if blink_depth < times {
+            match apply_blink_algo(val) {
+                BlinkResult::Replaced(new_val) => {
+                    self.blink_recursion(
+                        new_val,
+                        blink_depth + 1,
+                        times,
+                        &mut stones_for_this_call,
+                    );
+                }
+                BlinkResult::Split(first, second) => {
+                    self.blink_recursion(first, blink_depth + 1, times, &mut stones_for_this_call);
+                    self.blink_recursion(second, blink_depth + 1, times, &mut stones_for_this_call);
+                    stones_for_this_call += 1;
+                }
+            }
+        }
+
+        // Update the total stones count
+        *num_of_stones += stones_for_this_call;
+
+        // Cache the result
+        let mut cache = self.cache.lock().unwrap();
+        cache.insert((val, blink_depth), stones_for_this_call);
+    }
+
+    pub fn blink_with_stack(val: u64, times: usize) -> usize {
+        let mut stack = vec![(val, 0)];
+        let mut num_of_stones = 0;
+
+        while let Some((current_val, blink_depth)) = stack.pop() {
+            if blink_depth == times {
+                continue;
+            }
+            match apply_blink_algo(current_val) {
+                BlinkResult::Replaced(new_val) => {
+                    stack.push((new_val, blink_depth + 1));
+                }
+                BlinkResult::Split(first, second) => {
+                    stack.push((first, blink_depth + 1));
+                    stack.push((second, blink_depth + 1));
+                    num_of_stones += 1;
+                }
+            }
+        }
+        num_of_stones
+    }
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Vec<u64>;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        get_initial_stones(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_answer(input, CALC_POINT_P1)
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        count_answer(input, CALC_POINT_P2)
+    }
 }
@@ -4,7 +4,8 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use aoc_2024_11::{apply_blink_algo, get_initial_stones, BlinkResult};
+use crate::{apply_blink_algo, get_initial_stones, BlinkResult};
+use aoc_common::ParseError;
 use rayon::{
     iter::{IndexedParallelIterator, ParallelIterator},
     slice::ParallelSlice,
@@ -21,8 +22,8 @@ pub trait Stones {
 pub struct StonesInRamListBased(pub Vec<u64>);
 
 impl StonesInRamListBased {
-    pub fn new(data: &[u8]) -> Self {
-        Self(get_initial_stones(data))
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        get_initial_stones(data).map(Self)
     }
 
     pub fn memory_usage(&self) -> usize {
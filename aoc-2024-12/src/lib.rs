@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use aoc_common::{Answer, ParseError, Solver};
+use aoc_grid::{Coord2D, Grid};
+
+#[derive(Debug)]
+pub struct GardenUnplotted {
+    pub garden: Grid<char>,
+}
+
+#[derive(Debug)]
+pub struct Plot {
+    pub plot_type: char,
+    tiles: Vec<Coord2D>,
+    fences: Vec<(Coord2D, EdgeDir)>,
+    fences_on_one_line: HashMap<(usize, EdgeDir), Vec<usize>>,
+}
+
+impl Plot {
+    pub fn new(plot_type: char) -> Self {
+        Self {
+            plot_type,
+            tiles: Vec::new(),
+            fences: Vec::new(),
+            fences_on_one_line: HashMap::new(),
+        }
+    }
+
+    pub fn area(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn perimeter(&self) -> usize {
+        self.fences.len()
+    }
+
+    pub fn cost_p1(&self) -> usize {
+        self.area() * self.perimeter()
+    }
+
+    pub fn cost_p2(&self) -> usize {
+        self.area() * self.sides()
+    }
+
+    pub fn sides(&self) -> usize {
+        let mut sides = 0;
+        for fences_on_one_line in self.fences_on_one_line.values() {
+            sides += 1;
+            assert!(fences_on_one_line.is_sorted());
+            for (seg_first, seg_second) in fences_on_one_line
+                .iter()
+                .zip(fences_on_one_line.iter().skip(1))
+            {
+                if seg_second - seg_first > 1 {
+                    sides += 1;
+                }
+            }
+        }
+        sides
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub enum EdgeDir {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl GardenUnplotted {
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        Ok(Self {
+            garden: Grid::parse_with(data, "garden", |_, plot_type| {
+                plot_type.is_ascii_alphabetic().then_some(plot_type as char)
+            })?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Garden {
+    inner: GardenUnplotted,
+    pub plots: HashMap<char, Vec<Plot>>,
+}
+
+impl Garden {
+    pub fn new(unplotted: GardenUnplotted) -> Self {
+        Garden {
+            inner: unplotted,
+            plots: Default::default(),
+        }
+    }
+
+    pub fn total_cost_p1(&self) -> usize {
+        self.plots
+            .values()
+            .map(|plots| plots.iter().map(|plot| plot.cost_p1()).sum::<usize>())
+            .sum()
+    }
+
+    pub fn total_cost_p2(&self) -> usize {
+        self.plots
+            .values()
+            .map(|plots| plots.iter().map(|plot| plot.cost_p2()).sum::<usize>())
+            .sum()
+    }
+
+    pub fn find_all_plots(&mut self) {
+        let mut visited_tiles: HashMap<Coord2D, char> = HashMap::new();
+        for (tile, plot_type) in self.inner.garden.iter() {
+            if visited_tiles.contains_key(&tile) {
+                continue;
+            }
+
+            let mut plot = Plot::new(*plot_type);
+            self.find_plot_recursive(
+                &self.inner.garden,
+                &mut plot,
+                &mut visited_tiles,
+                tile.x,
+                tile.y,
+            );
+            match self.plots.entry(*plot_type) {
+                std::collections::hash_map::Entry::Occupied(mut occupied_entry) => {
+                    occupied_entry.get_mut().push(plot);
+                }
+                std::collections::hash_map::Entry::Vacant(vacant_entry) => {
+                    vacant_entry.insert(vec![plot]);
+                }
+            }
+        }
+        self.plots.values_mut().for_each(|plots_list| {
+            plots_list.iter_mut().for_each(|plot| {
+                plot.fences_on_one_line
+                    .values_mut()
+                    .for_each(|fences_on_one_line| {
+                        fences_on_one_line.sort_unstable();
+                    });
+            });
+        });
+    }
+
+    pub fn find_plot_recursive(
+        &self,
+        garden: &Grid<char>,
+        plot: &mut Plot,
+        visited: &mut HashMap<Coord2D, char>,
+        x: usize,
+        y: usize,
+    ) {
+        let here = Coord2D::new(x, y);
+        if visited.contains_key(&here) {
+            return;
+        }
+        plot.tiles.push(here);
+        visited.insert(here, plot.plot_type);
+
+        // North
+        if x > 0 && garden[(x - 1, y)] == plot.plot_type {
+            self.find_plot_recursive(garden, plot, visited, x - 1, y);
+        }
+        if x == 0 || garden[(x - 1, y)] != plot.plot_type {
+            plot.fences.push((here, EdgeDir::North));
+            plot.fences_on_one_line
+                .entry((x, EdgeDir::North))
+                .or_default()
+                .push(y);
+        }
+        // South
+        if x < garden.rows() - 1 && garden[(x + 1, y)] == plot.plot_type {
+            self.find_plot_recursive(garden, plot, visited, x + 1, y);
+        }
+        if x == garden.rows() - 1 || garden[(x + 1, y)] != plot.plot_type {
+            plot.fences.push((here, EdgeDir::South));
+            plot.fences_on_one_line
+                .entry((x, EdgeDir::South))
+                .or_default()
+                .push(y);
+        }
+        // East
+        if y < garden.cols() - 1 && garden[(x, y + 1)] == plot.plot_type {
+            self.find_plot_recursive(garden, plot, visited, x, y + 1);
+        }
+        if y == garden.cols() - 1 || garden[(x, y + 1)] != plot.plot_type {
+            plot.fences.push((here, EdgeDir::East));
+            plot.fences_on_one_line
+                .entry((y, EdgeDir::East))
+                .or_default()
+                .push(x);
+        }
+        // West
+        if y > 0 && garden[(x, y - 1)] == plot.plot_type {
+            self.find_plot_recursive(garden, plot, visited, x, y - 1);
+        }
+        if y == 0 || garden[(x, y - 1)] != plot.plot_type {
+            plot.fences.push((here, EdgeDir::West));
+            plot.fences_on_one_line
+                .entry((y, EdgeDir::West))
+                .or_default()
+                .push(x);
+        }
+    }
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = Garden;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        let mut garden = Garden::new(GardenUnplotted::new(data)?);
+        garden.find_all_plots();
+        Ok(garden)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.total_cost_p1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.total_cost_p2().into()
+    }
+}
@@ -0,0 +1,156 @@
+use aoc_common::{input_lines, Answer, Line, ParseError, Solver};
+use regex::Regex;
+
+const DEBUG: bool = false;
+
+#[derive(Default)]
+pub enum ParseState {
+    #[default]
+    ReadButtonsA,
+    ReadButtonsB,
+    ReadPrize,
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct LinSys2x2 {
+    pub col0: (u64, u64),
+    pub col1: (u64, u64),
+    pub b: (u64, u64),
+}
+
+impl LinSys2x2 {
+    pub fn determinant(&self) -> i64 {
+        (self.col0.0 * self.col1.1) as i64 - (self.col1.0 * self.col0.1) as i64
+    }
+
+    pub fn determinant_col0(&self) -> i64 {
+        (self.b.0 * self.col1.1) as i64 - (self.col1.0 * self.b.1) as i64
+    }
+
+    pub fn determinant_col1(&self) -> i64 {
+        (self.b.1 * self.col0.0) as i64 - (self.col0.1 * self.b.0) as i64
+    }
+
+    pub fn change_position_p2(&mut self) {
+        self.b.0 += 10000000000000;
+        self.b.1 += 10000000000000;
+    }
+
+    pub fn compute_solution(&self) -> Option<(u64, u64)> {
+        let det = self.determinant();
+        if det == 0 {
+            return None;
+        }
+        let (mut button_presses, mut button_presses2) = (0, 0);
+        if self.determinant() != 0 {
+            let det_col0 = self.determinant_col0();
+            if det_col0 % det != 0 {
+                return None;
+            }
+            let det_col1 = self.determinant_col1();
+            if det_col1 % det != 0 {
+                return None;
+            }
+            button_presses = det_col0 / det;
+            button_presses2 = det_col1 / det;
+        }
+        Some((button_presses as u64, button_presses2 as u64))
+    }
+}
+
+/// Parses the two numbers of a button or prize line.
+fn parse_pair(regex: &Regex, line: &Line, expected: &str) -> Result<(u64, u64), ParseError> {
+    let captures = regex
+        .captures(line.text)
+        .ok_or_else(|| line.error(0, format!("expected {}", expected)))?;
+    Ok((
+        line.parse(captures.get(1).unwrap().as_str(), "a number")?,
+        line.parse(captures.get(2).unwrap().as_str(), "a number")?,
+    ))
+}
+
+pub fn parse_claw_machines(data: &[u8]) -> Result<Vec<LinSys2x2>, ParseError> {
+    let mut les_list = Vec::new();
+    let mut next_les = LinSys2x2::default();
+    let mut parse_state = ParseState::default();
+    let regex_buttons = Regex::new(r"\w*: X\+(\d+), Y\+(\d+)").unwrap();
+    let regex_prize = Regex::new(r"\w*: X\=(\d+), Y\=(\d+)").unwrap();
+
+    for line in input_lines(data)? {
+        if line.text.is_empty() {
+            continue;
+        }
+        match parse_state {
+            ParseState::ReadButtonsA => {
+                next_les.col0 = parse_pair(&regex_buttons, &line, "button A")?;
+                parse_state = ParseState::ReadButtonsB;
+            }
+            ParseState::ReadButtonsB => {
+                next_les.col1 = parse_pair(&regex_buttons, &line, "button B")?;
+                parse_state = ParseState::ReadPrize;
+            }
+            ParseState::ReadPrize => {
+                next_les.b = parse_pair(&regex_prize, &line, "prize location")?;
+                les_list.push(next_les);
+                next_les = Default::default();
+                parse_state = ParseState::ReadButtonsA;
+            }
+        }
+    }
+    if !matches!(parse_state, ParseState::ReadButtonsA) {
+        return Err(ParseError::input(
+            "incomplete claw machine at the end of the input",
+        ));
+    }
+    Ok(les_list)
+}
+
+pub fn tokens_p1(les_list: &[LinSys2x2]) -> u64 {
+    let mut tokens = 0;
+    for les in les_list {
+        if let Some((a_presses, b_presses)) = les.compute_solution() {
+            if a_presses > 100 || b_presses > 100 {
+                continue;
+            }
+            if DEBUG {
+                println!("solution: {:?}", (a_presses, b_presses));
+            }
+            tokens += a_presses * 3 + b_presses;
+        }
+    }
+    tokens
+}
+
+pub fn tokens_p2(les_list: &[LinSys2x2]) -> u64 {
+    let mut tokens = 0;
+    for les in les_list {
+        let mut les = *les;
+        les.change_position_p2();
+
+        if let Some((a_presses, b_presses)) = les.compute_solution() {
+            if DEBUG {
+                println!("solution: {:?}", (a_presses, b_presses));
+            }
+            tokens += a_presses * 3 + b_presses;
+        }
+    }
+    tokens
+}
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input = Vec<LinSys2x2>;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_claw_machines(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        tokens_p1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        tokens_p2(input).into()
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::{input_lines, Answer, InputMeta, ParseError, Solver};
+use aoc_grid::{Coord2D, ICoord2D};
+use regex::Regex;
+
+/// Dimensions of the bathroom for the puzzle input. Other inputs specify their dimensions with
+/// the `x_dim` and `y_dim` metadata parameters.
+pub const X_DIM_INPUT: usize = 101;
+pub const Y_DIM_INPUT: usize = 103;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Robot {
+    pub position: Coord2D,
+    pub velocity: ICoord2D,
+}
+
+#[derive(Debug, Clone)]
+pub struct Bathroom {
+    pub robots: Vec<Robot>,
+    pub x_dim: usize,
+    pub y_dim: usize,
+}
+
+impl Bathroom {
+    pub fn new_one_robot(robot: Robot, x_dim: usize, y_dim: usize) -> Self {
+        Self {
+            robots: vec![robot],
+            x_dim,
+            y_dim,
+        }
+    }
+
+    pub fn new(data: &[u8], x_dim: usize, y_dim: usize) -> Result<Self, ParseError> {
+        let re_robot = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
+        let mut robot = Robot::default();
+        let mut robots = Vec::new();
+        for line in input_lines(data)? {
+            let values = re_robot
+                .captures(line.text)
+                .ok_or_else(|| line.error(0, "expected a robot like 'p=0,4 v=3,-3'"))?;
+            let value = |idx: usize| values.get(idx).unwrap().as_str();
+            robot.position.x = line.parse(value(1), "a position")?;
+            robot.position.y = line.parse(value(2), "a position")?;
+            robot.velocity.x = line.parse(value(3), "a velocity")?;
+            robot.velocity.y = line.parse(value(4), "a velocity")?;
+            if robot.position.x >= x_dim || robot.position.y >= y_dim {
+                return Err(line.error(
+                    line.column_of(value(1)),
+                    format!("robot outside of the {}x{} bathroom", x_dim, y_dim),
+                ));
+            }
+            robots.push(robot);
+        }
+        Ok(Self {
+            robots,
+            x_dim,
+            y_dim,
+        })
+    }
+
+    pub fn safety_factor(&self) -> usize {
+        let mut robots_ne = 0;
+        let mut robots_nw = 0;
+        let mut robots_se = 0;
+        let mut robots_sw = 0;
+        for robot in self.robots.iter() {
+            #[allow(clippy::comparison_chain)]
+            if robot.position.x < self.x_dim / 2 {
+                if robot.position.y < self.y_dim / 2 {
+                    robots_nw += 1;
+                } else if robot.position.y > self.y_dim / 2 {
+                    robots_sw += 1;
+                }
+            } else if robot.position.x > self.x_dim / 2 {
+                if robot.position.y < self.y_dim / 2 {
+                    robots_ne += 1;
+                } else if robot.position.y > self.y_dim / 2 {
+                    robots_se += 1;
+                }
+            }
+        }
+        robots_nw * robots_ne * robots_sw * robots_se
+    }
+
+    /// Renders the robot positions, with `#` for tiles occupied by at least one robot.
+    pub fn render(&self) -> String {
+        let mut hash_map = HashSet::new();
+        let mut xmas_tree = String::new();
+        for robot in self.robots.iter() {
+            hash_map.insert((robot.position.x, robot.position.y));
+        }
+        for y in 0..self.y_dim {
+            for x in 0..self.x_dim {
+                if hash_map.contains(&(x, y)) {
+                    xmas_tree.push('#');
+                } else {
+                    xmas_tree.push('.');
+                }
+            }
+            xmas_tree.push('\n');
+        }
+        xmas_tree
+    }
+
+    pub fn step(&mut self) {
+        for robot in self.robots.iter_mut() {
+            // Robots leaving the bathroom on one side enter it again on the opposite side.
+            robot.position = robot
+                .position
+                .wrapping_offset(robot.velocity, self.x_dim, self.y_dim);
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct BathroomXmasPattern {
+    pub x_map: HashMap<usize, Vec<usize>>,
+    pub y_map: HashMap<usize, Vec<usize>>,
+}
+
+impl BathroomXmasPattern {
+    pub fn update(&mut self, robots: &[Robot]) {
+        self.x_map.clear();
+        self.y_map.clear();
+        for robot in robots.iter() {
+            self.x_map
+                .entry(robot.position.x)
+                .or_default()
+                .push(robot.position.y);
+            self.y_map
+                .entry(robot.position.y)
+                .or_default()
+                .push(robot.position.x);
+        }
+        for lists in self.x_map.values_mut() {
+            lists.sort_unstable();
+        }
+        for lists in self.y_map.values_mut() {
+            lists.sort_unstable();
+        }
+    }
+
+    pub fn lines_occuring(&self, pos_map: &HashMap<usize, Vec<usize>>) -> u32 {
+        let mut line_occured = 0;
+        let threshold = 5;
+        for lists in pos_map.values() {
+            let mut line_found = false;
+            let mut prev_diff = 0;
+            let mut counter = 0;
+            for (next, last) in lists.iter().skip(1).zip(lists.iter()) {
+                let next_diff = next - last;
+                if next_diff > 0 {
+                    if next_diff == prev_diff {
+                        counter += 1;
+                    } else {
+                        counter = 0;
+                        prev_diff = next_diff;
+                        line_found = false;
+                    }
+                    if counter >= threshold && !line_found {
+                        line_occured += 1;
+                        line_found = true;
+                    }
+                }
+            }
+        }
+        line_occured
+    }
+
+    pub fn horiz_lines_occuring(&self) -> u32 {
+        self.lines_occuring(&self.x_map)
+    }
+
+    pub fn vert_lines_occuring(&self) -> u32 {
+        self.lines_occuring(&self.y_map)
+    }
+}
+
+pub fn safety_factor_after(bathroom: &Bathroom, seconds: usize) -> usize {
+    let mut bathroom = bathroom.clone();
+    for _ in 0..seconds {
+        bathroom.step();
+    }
+    bathroom.safety_factor()
+}
+
+/// Steps the robots until they form line patterns, which is the case for the christmas tree
+/// easter egg. Returns the time at which the pattern was detected together with the bathroom state
+/// at that time.
+pub fn find_xmas_tree(bathroom: &Bathroom, max_time: usize) -> Option<(usize, Bathroom)> {
+    let mut bathroom = bathroom.clone();
+    let mut pattern_recognition = BathroomXmasPattern::default();
+    for time in 1..max_time {
+        bathroom.step();
+        pattern_recognition.update(&bathroom.robots);
+        if pattern_recognition.horiz_lines_occuring() >= 2
+            || pattern_recognition.vert_lines_occuring() >= 2
+        {
+            return Some((time, bathroom));
+        }
+    }
+    None
+}
+
+/// Bathroom dimensions of the selected input.
+pub fn bathroom_dims(meta: &InputMeta) -> Result<(usize, usize), ParseError> {
+    Ok((
+        meta.get_or("x_dim", X_DIM_INPUT)?,
+        meta.get_or("y_dim", Y_DIM_INPUT)?,
+    ))
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input = Bathroom;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        Bathroom::new(data, X_DIM_INPUT, Y_DIM_INPUT)
+    }
+
+    fn parse_with_meta(data: &[u8], meta: &InputMeta) -> Result<Self::Input, ParseError> {
+        let (x_dim, y_dim) = bathroom_dims(meta)?;
+        Bathroom::new(data, x_dim, y_dim)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        safety_factor_after(input, 100).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        match find_xmas_tree(input, 100000) {
+            Some((time, _)) => time.into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
@@ -0,0 +1,554 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::{input_lines, Answer, Line, ParseError, Solver};
+use aoc_grid::{Direction, Grid};
+
+const DEBUG_P1: bool = false;
+pub const DEBUG_P2: bool = false;
+
+/// Glyphs of the warehouse map: walls, free space, crates and the robot.
+const WAREHOUSE_GLYPHS: &[u8] = b"#.O@";
+
+pub type Coord = (usize, usize);
+
+#[derive(Debug, Clone)]
+pub struct WarehouseCommon {
+    pub x_dim: usize,
+    pub y_dim: usize,
+    pub robot: Coord,
+    pub walls: HashSet<Coord>,
+}
+
+impl WarehouseCommon {
+    pub fn next_move_into_border(&self, dir: Direction, coord: Coord) -> bool {
+        match dir {
+            Direction::North => {
+                if coord.0 == 0 {
+                    return true;
+                }
+            }
+            Direction::South => {
+                if coord.0 >= self.x_dim - 1 {
+                    return true;
+                }
+            }
+            Direction::West => {
+                if coord.1 == 0 {
+                    return true;
+                }
+            }
+            Direction::East => {
+                if coord.1 >= self.y_dim - 1 {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    pub fn apply_movement(movement: Direction, mut coords: (usize, usize)) -> (usize, usize) {
+        match movement {
+            Direction::North => coords.0 -= 1,
+            Direction::South => coords.0 += 1,
+            Direction::West => coords.1 -= 1,
+            Direction::East => coords.1 += 1,
+        }
+        coords
+    }
+
+    /// Parses the movements which follow the warehouse map after an empty line.
+    pub fn parse_movements(data: &[u8], movements: &mut Vec<Direction>) -> Result<(), ParseError> {
+        for line in input_lines(data)?
+            .skip_while(|line| !line.text.is_empty())
+            .skip(1)
+        {
+            Self::parse_movement_line(&line, movements)?;
+        }
+        Ok(())
+    }
+
+    pub fn parse_movement_line(
+        line: &Line,
+        movements: &mut Vec<Direction>,
+    ) -> Result<(), ParseError> {
+        for (idx, c) in line.text.char_indices() {
+            match c {
+                '>' => movements.push(Direction::East),
+                '<' => movements.push(Direction::West),
+                '^' => movements.push(Direction::North),
+                'v' => movements.push(Direction::South),
+                _ => return Err(line.unexpected(idx, "movements")),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Warehouse {
+    pub inner: WarehouseCommon,
+    pub crates: HashSet<Coord>,
+}
+
+impl Warehouse {
+    pub fn new(data: &[u8], movements: &mut Vec<Direction>) -> Result<Self, ParseError> {
+        // The walls around the warehouse are implied by the border checks.
+        let grid = Grid::parse(data, "warehouse", WAREHOUSE_GLYPHS)?.without_border();
+        WarehouseCommon::parse_movements(data, movements)?;
+        Ok(Self {
+            inner: WarehouseCommon {
+                x_dim: grid.rows(),
+                y_dim: grid.cols(),
+                walls: grid.find_all(b'#').map(Into::into).collect(),
+                robot: grid.find_required(b'@', "warehouse")?.into(),
+            },
+            crates: grid.find_all(b'O').map(Into::into).collect(),
+        })
+    }
+
+    pub fn apply_movement_wide_crate(movement: Direction, mut wide_crate: WideCrate) -> WideCrate {
+        match movement {
+            Direction::North => {
+                wide_crate.left.0 -= 1;
+                wide_crate.right.0 -= 1;
+            }
+            Direction::South => {
+                wide_crate.left.0 += 1;
+                wide_crate.right.0 += 1;
+            }
+            Direction::West => {
+                wide_crate.left.1 -= 1;
+                wide_crate.right.1 -= 1;
+            }
+            Direction::East => {
+                wide_crate.left.1 += 1;
+                wide_crate.right.1 += 1;
+            }
+        }
+        wide_crate
+    }
+
+    pub fn sum_of_gps(&self) -> usize {
+        let mut sum = 0;
+        for wcrate in self.crates.iter() {
+            sum += (wcrate.0 + 1) * 100 + wcrate.1 + 1;
+        }
+        sum
+    }
+
+    pub fn move_robot(&mut self, movements: &[Direction]) {
+        for &movement in movements {
+            if self.inner.next_move_into_border(movement, self.inner.robot) {
+                continue;
+            }
+            let next_coord = WarehouseCommon::apply_movement(movement, self.inner.robot);
+            if self.inner.walls.contains(&next_coord) {
+                continue;
+            }
+            let mut move_robot = true;
+            if self.crates.contains(&next_coord) {
+                let mut move_coord = next_coord;
+                loop {
+                    if self.inner.next_move_into_border(movement, move_coord) {
+                        move_robot = false;
+                        break;
+                    }
+                    move_coord = WarehouseCommon::apply_movement(movement, move_coord);
+                    if self.crates.contains(&move_coord) {
+                        continue;
+                    }
+                    if self.inner.walls.contains(&move_coord) {
+                        move_robot = false;
+                        break;
+                    }
+                    self.crates.remove(&next_coord);
+                    self.crates.insert(move_coord);
+                    break;
+                }
+            }
+            if move_robot {
+                self.inner.robot = next_coord;
+            }
+            if DEBUG_P1 {
+                println!("robot: {:?}", self.inner.robot);
+                println!("crates: {:?}", self.crates);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct WideCrate {
+    left: Coord,
+    right: Coord,
+}
+
+#[derive(Debug, Clone)]
+pub struct WideWarehouse {
+    pub inner: WarehouseCommon,
+    pub crate_locations: HashMap<Coord, usize>,
+    pub wide_crates: Vec<WideCrate>,
+}
+
+impl WideWarehouse {
+    pub fn new(
+        robot: Coord,
+        x_dim: usize,
+        y_dim: usize,
+        walls: HashSet<Coord>,
+        wide_crates: HashSet<WideCrate>,
+    ) -> Self {
+        let mut wide_crate_list = Vec::new();
+        let mut crate_locations = HashMap::new();
+        for (idx, wide_crate) in wide_crates.iter().enumerate() {
+            wide_crate_list.push(*wide_crate);
+            crate_locations.insert(wide_crate.left, idx);
+            crate_locations.insert(wide_crate.right, idx);
+        }
+        Self {
+            inner: WarehouseCommon {
+                x_dim,
+                y_dim,
+                robot,
+                walls,
+            },
+            crate_locations,
+            wide_crates: wide_crate_list,
+        }
+    }
+    pub fn new_from_data(data: &[u8], movements: &mut Vec<Direction>) -> Result<Self, ParseError> {
+        let grid = Grid::parse(data, "warehouse", WAREHOUSE_GLYPHS)?.without_border();
+        WarehouseCommon::parse_movements(data, movements)?;
+        let robot = grid.find_required(b'@', "warehouse")?;
+        let robot = (robot.x, robot.y * 2);
+        let mut walls = HashSet::new();
+        let mut wide_crates = Vec::new();
+        let mut crate_locations = HashMap::new();
+        for (pos, c) in grid.iter() {
+            let (x_coord, y_coord) = (pos.x, pos.y * 2);
+            match c {
+                b'#' => {
+                    walls.insert((x_coord, y_coord));
+                    walls.insert((x_coord, y_coord + 1));
+                }
+                b'O' => {
+                    crate_locations.insert((x_coord, y_coord), wide_crates.len());
+                    crate_locations.insert((x_coord, y_coord + 1), wide_crates.len());
+                    wide_crates.push(WideCrate {
+                        left: (x_coord, y_coord),
+                        right: (x_coord, y_coord + 1),
+                    });
+                }
+                _ => (),
+            }
+        }
+        Ok(Self {
+            inner: WarehouseCommon {
+                x_dim: grid.rows(),
+                y_dim: grid.cols() * 2,
+                walls,
+                robot,
+            },
+            crate_locations,
+            wide_crates,
+        })
+    }
+
+    pub fn print_warehouse(&self) {
+        for x in 0..self.inner.x_dim {
+            for y in 0..self.inner.y_dim {
+                let coord = (x, y);
+                if self.inner.walls.contains(&coord) {
+                    print!("#");
+                } else if self.crate_locations.contains_key(&coord) {
+                    let wcrate_idx = self.crate_locations[&coord];
+                    if self.wide_crates[wcrate_idx].left == coord {
+                        print!("[");
+                    } else {
+                        print!("]");
+                    }
+                } else if self.inner.robot == coord {
+                    print!("@");
+                } else {
+                    print!(".");
+                }
+            }
+            println!();
+        }
+    }
+    pub fn handle_crate_up_down_dir_llm(
+        &self,
+        movement: Direction,
+        next_coord: Coord,
+        crates_to_move: &mut Vec<usize>,
+    ) -> bool {
+        let mut move_robot = true;
+        let mut crates_to_check: HashSet<usize> =
+            HashSet::from([self.crate_locations[&next_coord]]);
+        let mut next_crates_to_check = HashSet::new();
+
+        while move_robot && !crates_to_check.is_empty() {
+            for &wcrate_idx in &crates_to_check {
+                let wide_crate = self.wide_crates[wcrate_idx];
+
+                // Determine next coordinates based on the direction.
+                let (next_coord_left, next_coord_right) = match movement {
+                    Direction::North => (
+                        (wide_crate.left.0.wrapping_sub(1), wide_crate.left.1),
+                        (wide_crate.right.0.wrapping_sub(1), wide_crate.right.1),
+                    ),
+                    Direction::South => (
+                        (wide_crate.left.0 + 1, wide_crate.left.1),
+                        (wide_crate.right.0 + 1, wide_crate.right.1),
+                    ),
+                    _ => unreachable!(),
+                };
+
+                // Check for boundaries and walls.
+                if next_coord_left.0 >= self.inner.x_dim
+                    || next_coord_right.0 >= self.inner.x_dim
+                    || self.inner.walls.contains(&next_coord_left)
+                    || self.inner.walls.contains(&next_coord_right)
+                {
+                    move_robot = false;
+                    break;
+                }
+
+                // Add the current crate to the list of crates to move.
+                crates_to_move.push(wcrate_idx);
+
+                // Check for crates in the next positions.
+                for &coord in &[next_coord_left, next_coord_right] {
+                    if let Some(&next_crate_idx) = self.crate_locations.get(&coord) {
+                        next_crates_to_check.insert(next_crate_idx);
+                    }
+                }
+            }
+
+            // Swap and clear sets for the next iteration.
+            std::mem::swap(&mut crates_to_check, &mut next_crates_to_check);
+            next_crates_to_check.clear();
+        }
+
+        move_robot
+    }
+
+    pub fn handle_crate_up_down_dir(
+        &self,
+        movement: Direction,
+        next_coord: Coord,
+        crates_to_move: &mut Vec<usize>,
+    ) -> bool {
+        let mut move_robot = true;
+
+        let mut crates_to_check: HashSet<usize> =
+            HashSet::from([self.crate_locations[&next_coord]]);
+        let mut next_crates_to_check = HashSet::new();
+        while move_robot && !crates_to_check.is_empty() {
+            for &wcrate_idx in &crates_to_check {
+                let wide_crate = self.wide_crates[wcrate_idx];
+                let next_coord_left;
+                let next_coord_right;
+                if movement == Direction::North {
+                    if wide_crate.left.0 == 0 {
+                        move_robot = false;
+                        break;
+                    }
+                    next_coord_left = (wide_crate.left.0 - 1, wide_crate.left.1);
+                    next_coord_right = (wide_crate.right.0 - 1, wide_crate.right.1);
+                } else {
+                    if wide_crate.left.0 == self.inner.x_dim - 1 {
+                        move_robot = false;
+                        break;
+                    }
+                    next_coord_left = (wide_crate.left.0 + 1, wide_crate.left.1);
+                    next_coord_right = (wide_crate.right.0 + 1, wide_crate.right.1);
+                }
+                if self.inner.walls.contains(&next_coord_left)
+                    || self.inner.walls.contains(&next_coord_right)
+                {
+                    move_robot = false;
+                    break;
+                }
+                crates_to_move.push(wcrate_idx);
+
+                // Check for crates in the next positions.
+                for &coord in &[next_coord_left, next_coord_right] {
+                    if let Some(&next_crate_idx) = self.crate_locations.get(&coord) {
+                        next_crates_to_check.insert(next_crate_idx);
+                    }
+                }
+            }
+            std::mem::swap(&mut crates_to_check, &mut next_crates_to_check);
+            next_crates_to_check.clear();
+        }
+        move_robot
+    }
+
+    pub fn handle_crate_left_right_dir(
+        &self,
+        movement: Direction,
+        next_coord: Coord,
+        crates_to_move: &mut Vec<usize>,
+    ) -> bool {
+        let mut move_robot = true;
+        // Similar to the default logic of part 1, but the crates are wider.
+        let mut current_coord = next_coord;
+        crates_to_move.push(self.crate_locations[&current_coord]);
+        loop {
+            if movement == Direction::West {
+                if current_coord.1 == 1 {
+                    move_robot = false;
+                    break;
+                }
+                current_coord.1 = current_coord.1.saturating_sub(2);
+            } else {
+                if current_coord.1 == self.inner.y_dim - 2 {
+                    move_robot = false;
+                    break;
+                }
+                current_coord.1 += 2;
+            }
+            if self.inner.walls.contains(&current_coord) {
+                move_robot = false;
+                break;
+            }
+            // There are no further crates to push.
+            if !self.crate_locations.contains_key(&current_coord) {
+                break;
+            }
+            // There is another crate we can push.
+            crates_to_move.push(self.crate_locations[&current_coord]);
+        }
+        move_robot
+    }
+
+    pub fn move_robot(&mut self, movements: &[Direction]) {
+        for &movement in movements {
+            if DEBUG_P2 {
+                println!("next movement: {:?}", movement);
+            }
+            if self.inner.next_move_into_border(movement, self.inner.robot) {
+                continue;
+            }
+            let next_coord = WarehouseCommon::apply_movement(movement, self.inner.robot);
+            if self.inner.walls.contains(&next_coord) {
+                continue;
+            }
+            let mut move_robot = true;
+            if self.crate_locations.contains_key(&next_coord) {
+                let mut crates_to_move = Vec::new();
+                move_robot = match movement {
+                    Direction::North | Direction::South => {
+                        self.handle_crate_up_down_dir(movement, next_coord, &mut crates_to_move)
+                    }
+                    Direction::West | Direction::East => {
+                        // Similar to the default logic of part 1, but the crates are wider.
+                        self.handle_crate_left_right_dir(movement, next_coord, &mut crates_to_move)
+                    }
+                };
+                if move_robot {
+                    if DEBUG_P2 && !crates_to_move.is_empty() {
+                        println!("crates to move into {:?}: {:?}", movement, crates_to_move);
+                    }
+                    if !crates_to_move.is_empty() {
+                        self.translate_crates(movement, &crates_to_move);
+                    }
+                }
+            }
+            if move_robot {
+                self.inner.robot = next_coord;
+            }
+            if DEBUG_P2 {
+                self.print_warehouse();
+            }
+        }
+    }
+
+    pub fn sum_of_gps(&self) -> usize {
+        let mut sum = 0;
+        for wcrate in self.wide_crates.iter() {
+            sum += (wcrate.left.0 + 1) * 100 + wcrate.left.1 + 2;
+        }
+        sum
+    }
+
+    pub fn translate_crates(&mut self, dir: Direction, crate_indexes: &[usize]) {
+        crate_indexes.iter().for_each(|&idx| {
+            self.crate_locations
+                .remove(&self.wide_crates[idx].left)
+                .unwrap();
+            self.crate_locations
+                .remove(&self.wide_crates[idx].right)
+                .unwrap();
+        });
+
+        crate_indexes.iter().for_each(|&idx| match dir {
+            Direction::North => {
+                self.wide_crates[idx].left.0 -= 1;
+                self.wide_crates[idx].right.0 -= 1;
+            }
+            Direction::South => {
+                self.wide_crates[idx].left.0 += 1;
+                self.wide_crates[idx].right.0 += 1;
+            }
+            Direction::West => {
+                self.wide_crates[idx].left.1 -= 1;
+                self.wide_crates[idx].right.1 -= 1;
+            }
+            Direction::East => {
+                self.wide_crates[idx].left.1 += 1;
+                self.wide_crates[idx].right.1 += 1;
+            }
+        });
+
+        crate_indexes.iter().for_each(|&idx| {
+            assert!(self
+                .crate_locations
+                .insert(self.wide_crates[idx].left, idx)
+                .is_none());
+            assert!(self
+                .crate_locations
+                .insert(self.wide_crates[idx].right, idx)
+                .is_none());
+        });
+    }
+}
+
+/// Both warehouse variants parsed from the same input, together with the robot movements.
+#[derive(Debug, Clone)]
+pub struct Warehouses {
+    pub warehouse: Warehouse,
+    pub wide_warehouse: WideWarehouse,
+    pub movements: Vec<Direction>,
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input = Warehouses;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        let mut movements = Vec::new();
+        let warehouse = Warehouse::new(data, &mut movements)?;
+        // The movements are identical for both warehouses.
+        let wide_warehouse = WideWarehouse::new_from_data(data, &mut Vec::new())?;
+        Ok(Warehouses {
+            warehouse,
+            wide_warehouse,
+            movements,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut warehouse = input.warehouse.clone();
+        warehouse.move_robot(&input.movements);
+        warehouse.sum_of_gps().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        let mut wide_warehouse = input.wide_warehouse.clone();
+        wide_warehouse.move_robot(&input.movements);
+        wide_warehouse.sum_of_gps().into()
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use aoc_common::{Answer, ParseError, Solver};
+use aoc_grid::{Coord2D, Direction, Grid};
+use aoc_search::dijkstra_all;
+
+#[derive(Debug)]
+pub struct Maze {
+    /// The maze without its outer wall.
+    pub grid: Grid<u8>,
+    pub start: Coord2D,
+    pub end: Coord2D,
+}
+
+/// Search state of the reindeer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Reindeer {
+    pub coord: Coord2D,
+    pub direction: Direction,
+}
+
+impl Maze {
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        let grid = Grid::parse(data, "maze", b"#SE.")?.without_border();
+        Ok(Self {
+            start: grid.find_required(b'S', "maze")?,
+            end: grid.find_required(b'E', "maze")?,
+            grid,
+        })
+    }
+
+    /// Possible moves of the reindeer together with their score. Turns are really expensive
+    /// compared to moving forward.
+    fn moves(&self, reindeer: &Reindeer) -> impl Iterator<Item = (Reindeer, usize)> + '_ {
+        let reindeer = *reindeer;
+        let forward = self
+            .grid
+            .step(reindeer.coord, reindeer.direction)
+            .filter(|&next| self.grid[next] != b'#')
+            .map(|coord| (Reindeer { coord, ..reindeer }, 1));
+        let turns = [reindeer.direction.cw(), reindeer.direction.ccw()].map(|direction| {
+            (
+                Reindeer {
+                    direction,
+                    ..reindeer
+                },
+                1000,
+            )
+        });
+        forward.into_iter().chain(turns)
+    }
+
+    /// Returns the lowest possible score and all tiles which are part of at least one of the
+    /// cheapest paths.
+    pub fn find_cheapest_paths(&self) -> Option<(usize, HashSet<Coord2D>)> {
+        let start = Reindeer {
+            coord: self.start,
+            direction: Direction::East,
+        };
+        let all_paths = dijkstra_all(
+            start,
+            |reindeer| self.moves(reindeer),
+            |reindeer| reindeer.coord == self.end,
+        )?;
+        let best_seats = all_paths
+            .states_on_paths()
+            .into_iter()
+            .map(|reindeer| reindeer.coord)
+            .collect();
+        Some((all_paths.cost, best_seats))
+    }
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Input = Maze;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        Maze::new(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        match input.find_cheapest_paths() {
+            Some((cheapest, _)) => cheapest.into(),
+            None => Answer::Unsolved,
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        match input.find_cheapest_paths() {
+            Some((_, best_seats)) => best_seats.len().into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example0() {
+        let input_file = std::fs::read("example0.txt").unwrap();
+        let maze = Maze::new(&input_file).unwrap();
+        assert_eq!(maze.start, Coord2D::new(12, 0));
+        assert_eq!(maze.end, Coord2D::new(0, 12));
+        assert_eq!((maze.grid.rows(), maze.grid.cols()), (13, 13));
+        let (cheapest, best_seats) = maze.find_cheapest_paths().unwrap();
+        assert_eq!(cheapest, 7036);
+        assert_eq!(best_seats.len(), 45);
+    }
+}
@@ -0,0 +1,268 @@
+//! Assembler for the three-bit computer. Each line contains an instruction, a label or both:
+//!
+//! ```text
+//! loop:
+//!     adv 1      ; combo operands are a, b, c or a literal from 0 to 3
+//!     out a
+//!     jnz loop   ; jnz takes a label or a literal address
+//! ```
+//!
+//! Comments start with `;`. The operand of `bxc` is ignored by the computer and may be omitted.
+//! The `.byte` directive places 3-bit values into the memory as they are, for example `.byte 5, 7`
+//! for an instruction with the reserved combo operand.
+use std::collections::{hash_map::Entry, HashMap};
+
+use aoc_common::{Line, ParseError};
+
+use crate::Instruction;
+
+enum Code<'a> {
+    Instruction {
+        instruction: Instruction,
+        operand: Option<&'a str>,
+    },
+    Bytes(Vec<u8>),
+}
+
+struct Statement<'a> {
+    line: Line<'a>,
+    code: Code<'a>,
+}
+
+fn byte_values(line: &Line, values: &str) -> Result<Vec<u8>, ParseError> {
+    if values.trim().is_empty() {
+        return Err(line.error(text_end(line), ".byte needs at least one value"));
+    }
+    values
+        .split(',')
+        .map(
+            |value| match line.parse::<u8>(value, "a value from 0 to 7")? {
+                value @ 0..=7 => Ok(value),
+                _ => Err(line.expected(value.trim(), "a value from 0 to 7")),
+            },
+        )
+        .collect()
+}
+
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn literal_operand(line: &Line, part: &str) -> Result<u8, ParseError> {
+    match line.parse::<u8>(part, "a literal from 0 to 7")? {
+        value @ 0..=7 => Ok(value),
+        _ => Err(line.expected(part, "a literal from 0 to 7")),
+    }
+}
+
+fn combo_operand(line: &Line, part: &str) -> Result<u8, ParseError> {
+    match part.to_ascii_lowercase().as_str() {
+        "a" => return Ok(4),
+        "b" => return Ok(5),
+        "c" => return Ok(6),
+        _ => (),
+    }
+    match line.parse::<u8>(part, "a register or a literal from 0 to 3")? {
+        value @ 0..=3 => Ok(value),
+        7 => Err(line.error(line.column_of(part), "combo operand 7 is reserved")),
+        4..=6 => Err(line.error(
+            line.column_of(part),
+            "combo literals are 0 to 3, use a, b or c for the registers",
+        )),
+        _ => Err(line.expected(part, "a register or a literal from 0 to 3")),
+    }
+}
+
+fn jump_operand(line: &Line, part: &str, labels: &HashMap<&str, usize>) -> Result<u8, ParseError> {
+    if !is_label(part) {
+        return literal_operand(line, part);
+    }
+    let address = *labels
+        .get(part)
+        .ok_or_else(|| line.error(line.column_of(part), format!("undefined label {}", part)))?;
+    u8::try_from(address)
+        .ok()
+        .filter(|&address| address <= 7)
+        .ok_or_else(|| {
+            line.error(
+                line.column_of(part),
+                format!(
+                    "label {} at address {} is out of reach, jnz only jumps to 0 to 7",
+                    part, address
+                ),
+            )
+        })
+}
+
+/// Assembles the program source into the memory of the computer.
+pub fn assemble(source: &str) -> Result<Vec<u8>, ParseError> {
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut address = 0;
+    for (idx, text) in source.lines().enumerate() {
+        let line = Line::new(idx + 1, text);
+        let mut code = text.split(';').next().unwrap_or_default().trim();
+        if let Some((label, rest)) = code.split_once(':') {
+            let label = label.trim();
+            if !is_label(label) {
+                return Err(line.expected(label, "a label"));
+            }
+            match labels.entry(label) {
+                Entry::Occupied(_) => {
+                    return Err(line.error(
+                        line.column_of(label),
+                        format!("label {} is already defined", label),
+                    ))
+                }
+                Entry::Vacant(entry) => entry.insert(address),
+            };
+            code = rest.trim();
+        }
+        let mut tokens = code.split_whitespace();
+        let Some(mnemonic) = tokens.next() else {
+            continue;
+        };
+        if mnemonic.eq_ignore_ascii_case(".byte") {
+            let values = byte_values(&line, &code[mnemonic.len()..])?;
+            address += values.len();
+            statements.push(Statement {
+                line,
+                code: Code::Bytes(values),
+            });
+            continue;
+        }
+        let instruction = Instruction::ALL
+            .into_iter()
+            .find(|instruction| instruction.mnemonic().eq_ignore_ascii_case(mnemonic))
+            .ok_or_else(|| line.expected(mnemonic, "an instruction"))?;
+        let operand = tokens.next();
+        if let Some(extra) = tokens.next() {
+            return Err(line.expected(extra, "the end of the instruction"));
+        }
+        address += 2;
+        statements.push(Statement {
+            line,
+            code: Code::Instruction {
+                instruction,
+                operand,
+            },
+        });
+    }
+
+    let mut memory = Vec::with_capacity(address);
+    for Statement { line, code } in statements {
+        let (instruction, operand) = match code {
+            Code::Instruction {
+                instruction,
+                operand,
+            } => (instruction, operand),
+            Code::Bytes(values) => {
+                memory.extend(values);
+                continue;
+            }
+        };
+        let op = match (instruction, operand) {
+            (Instruction::Bxc, None) => 0,
+            (_, None) => {
+                return Err(line.error(
+                    text_end(&line),
+                    format!("{} needs an operand", instruction.mnemonic()),
+                ))
+            }
+            (Instruction::Jnz, Some(part)) => jump_operand(&line, part, &labels)?,
+            (Instruction::Bxl | Instruction::Bxc, Some(part)) => literal_operand(&line, part)?,
+            (_, Some(part)) => combo_operand(&line, part)?,
+        };
+        memory.push(instruction as u8);
+        memory.push(op);
+    }
+    Ok(memory)
+}
+
+/// Column right after the code of the line, without the comment.
+fn text_end(line: &Line) -> usize {
+    let code = line.text.split(';').next().unwrap_or_default().trim_end();
+    code.chars().count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{disasm::Disassembly, Computer, RUN_STEPS};
+
+    #[test]
+    fn labels_and_operands() {
+        let program = assemble(
+            "; counts down from a
+            start: adv 1
+                   out a  ; print
+                   jnz start
+                   BXC",
+        )
+        .unwrap();
+        assert_eq!(program, vec![0, 1, 5, 4, 3, 0, 4, 0]);
+        let mut computer = Computer::new(2024, 0, 0);
+        assert_eq!(
+            computer.execute(&program, RUN_STEPS).unwrap(),
+            &[4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]
+        );
+    }
+
+    #[test]
+    fn disassembly_round_trip() {
+        let program = [2, 4, 1, 5, 7, 5, 1, 6, 4, 1, 5, 5, 0, 3, 3, 0];
+        let listing = Disassembly::new(&program).to_string();
+        assert_eq!(assemble(&listing).unwrap(), program);
+
+        let program = [5, 7, 6, 7, 3, 2, 0, 1, 2];
+        let listing = Disassembly::new(&program).to_string();
+        assert_eq!(assemble(&listing).unwrap(), program);
+    }
+
+    #[test]
+    fn bytes() {
+        let program = assemble("  .byte 5, 7\nl: .BYTE 6\n  out a\n  jnz l").unwrap();
+        assert_eq!(program, vec![5, 7, 6, 5, 4, 3, 2]);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |source| assemble(source).unwrap_err().to_string();
+        assert_eq!(error("out 7"), "1:5: combo operand 7 is reserved");
+        assert_eq!(
+            error("bst 5"),
+            "1:5: combo literals are 0 to 3, use a, b or c for the registers"
+        );
+        assert_eq!(
+            error("bxl 8"),
+            "1:5: expected a literal from 0 to 7, found \"8\""
+        );
+        assert_eq!(error("\n  jnz end"), "2:7: undefined label end");
+        assert_eq!(error("adv"), "1:4: adv needs an operand");
+        assert_eq!(error(".byte"), "1:6: .byte needs at least one value");
+        assert_eq!(
+            error(".byte 1, 8"),
+            "1:10: expected a value from 0 to 7, found \"8\""
+        );
+        assert_eq!(
+            error(".byte 256"),
+            "1:7: expected a value from 0 to 7, found \"256\""
+        );
+        assert_eq!(
+            error("mul a"),
+            "1:1: expected an instruction, found \"mul\""
+        );
+        assert_eq!(
+            error("x: out a\nx: out b"),
+            "2:1: label x is already defined"
+        );
+        assert_eq!(
+            error("bst a\nbst a\nbst a\nbst a\nfar: jnz far"),
+            "5:10: label far at address 8 is out of reach, jnz only jumps to 0 to 7"
+        );
+    }
+}
@@ -0,0 +1,424 @@
+//! Compiler which turns the program memory into pre-decoded basic blocks, so brute-force searches
+//! don't pay for fetching and decoding every instruction. Straight-line code runs without
+//! updating the instruction pointer, and the output is compared while the program runs instead of
+//! being collected.
+//!
+//! A `bxl` is merged into the instruction before it if that one writes B, or into a `bxc` right
+//! after it. This saves a dispatch for every `bxl` of the usual puzzle programs.
+use crate::{decode, ComboOperand, Computer, ExecResult, Execution, Instruction, Operand};
+
+/// Instruction which continues with the next address. Every combination of instruction and
+/// combo operand is its own operation, so no operand is decoded at run time. The operations which
+/// write B xor the result with the operand of the `bxl` instructions merged into them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    AdvLiteral(u8),
+    AdvA,
+    AdvB,
+    AdvC,
+    BdvLiteral(u8, u8),
+    BdvA(u8),
+    BdvB(u8),
+    BdvC(u8),
+    CdvLiteral(u8),
+    CdvA,
+    CdvB,
+    CdvC,
+    /// `bst` with a literal operand and the merged `bxl` instructions sets B to a constant.
+    SetB(u8),
+    BstA(u8),
+    BstB(u8),
+    BstC(u8),
+    OutLiteral(u8),
+    OutA,
+    OutB,
+    OutC,
+    Bxl(u8),
+    Bxc(u8),
+    /// Jump to the own address, which the computer ignores.
+    Nop,
+}
+
+impl Op {
+    /// Single operation with the effect of this one followed by `next`, if there is one.
+    fn merge(self, next: Op) -> Option<Op> {
+        let Op::Bxl(value) = next else {
+            // b ^ xor ^ c is the same as b ^ c ^ xor.
+            return match (self, next) {
+                (Op::Bxl(xor), Op::Bxc(value)) => Some(Op::Bxc(xor ^ value)),
+                _ => None,
+            };
+        };
+        Some(match self {
+            Op::BdvLiteral(shift, xor) => Op::BdvLiteral(shift, xor ^ value),
+            Op::BdvA(xor) => Op::BdvA(xor ^ value),
+            Op::BdvB(xor) => Op::BdvB(xor ^ value),
+            Op::BdvC(xor) => Op::BdvC(xor ^ value),
+            Op::SetB(xor) => Op::SetB(xor ^ value),
+            Op::BstA(xor) => Op::BstA(xor ^ value),
+            Op::BstB(xor) => Op::BstB(xor ^ value),
+            Op::BstC(xor) => Op::BstC(xor ^ value),
+            Op::Bxl(xor) => Op::Bxl(xor ^ value),
+            Op::Bxc(xor) => Op::Bxc(xor ^ value),
+            _ => return None,
+        })
+    }
+}
+
+/// How a basic block ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum End {
+    /// The next address is outside of the memory, so the program halts.
+    Halt,
+    /// Jump to the address if A is not zero.
+    Jnz(usize),
+    /// The next instruction faults.
+    Fault,
+}
+
+/// Straight-line code from a start address up to the next jump, fault or the end of the memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Block {
+    ops: Vec<Op>,
+    /// Number of instructions before the end, which is larger than the number of operations if
+    /// instructions were merged.
+    len: usize,
+    end: End,
+}
+
+/// Compiles the instruction at the address, or returns how the block ends there.
+fn compile_op(memory: &[u8], address: usize) -> Result<Op, End> {
+    if address >= memory.len() {
+        return Err(End::Halt);
+    }
+    let decoded = decode(memory, address).map_err(|_| End::Fault)?;
+    let combo = match decoded.operand {
+        Operand::Combo(ComboOperand::Reserved) => return Err(End::Fault),
+        Operand::Combo(combo) => combo,
+        Operand::Literal(value) => ComboOperand::Literal(value),
+        Operand::Ignored(_) => ComboOperand::Literal(0),
+    };
+    use ComboOperand::{Literal, A, B, C};
+    Ok(match (decoded.instruction, combo) {
+        (Instruction::Adv, Literal(value)) => Op::AdvLiteral(value),
+        (Instruction::Adv, A) => Op::AdvA,
+        (Instruction::Adv, B) => Op::AdvB,
+        (Instruction::Adv, C) => Op::AdvC,
+        (Instruction::Bdv, Literal(value)) => Op::BdvLiteral(value, 0),
+        (Instruction::Bdv, A) => Op::BdvA(0),
+        (Instruction::Bdv, B) => Op::BdvB(0),
+        (Instruction::Bdv, C) => Op::BdvC(0),
+        (Instruction::Cdv, Literal(value)) => Op::CdvLiteral(value),
+        (Instruction::Cdv, A) => Op::CdvA,
+        (Instruction::Cdv, B) => Op::CdvB,
+        (Instruction::Cdv, C) => Op::CdvC,
+        (Instruction::Bst, Literal(value)) => Op::SetB(value),
+        (Instruction::Bst, A) => Op::BstA(0),
+        (Instruction::Bst, B) => Op::BstB(0),
+        (Instruction::Bst, C) => Op::BstC(0),
+        (Instruction::Out, Literal(value)) => Op::OutLiteral(value),
+        (Instruction::Out, A) => Op::OutA,
+        (Instruction::Out, B) => Op::OutB,
+        (Instruction::Out, C) => Op::OutC,
+        (Instruction::Bxl, Literal(value)) => Op::Bxl(value),
+        (Instruction::Bxc, _) => Op::Bxc(0),
+        (Instruction::Jnz, Literal(target)) if target as usize == address => Op::Nop,
+        (Instruction::Jnz, Literal(target)) => return Err(End::Jnz(target as usize)),
+        _ => unreachable!("operand kind is determined by the instruction"),
+    })
+}
+
+fn compile_block(memory: &[u8], start: usize) -> Block {
+    let mut ops: Vec<Op> = Vec::new();
+    let mut len = 0;
+    loop {
+        match compile_op(memory, start + 2 * len) {
+            Ok(op) => {
+                match ops.last().and_then(|last| last.merge(op)) {
+                    Some(merged) => *ops.last_mut().unwrap() = merged,
+                    None => ops.push(op),
+                }
+                len += 1;
+            }
+            Err(end) => return Block { ops, len, end },
+        }
+    }
+}
+
+/// A program compiled for repeated execution. It behaves exactly like the program memory run by
+/// the [Computer].
+pub struct CompiledProgram {
+    /// Block for every start address, also the odd ones which can be reached by jumps.
+    blocks: Vec<Block>,
+    memory: Vec<u8>,
+}
+
+impl CompiledProgram {
+    pub fn new(memory: &[u8]) -> Self {
+        Self {
+            blocks: (0..memory.len())
+                .map(|start| compile_block(memory, start))
+                .collect(),
+            memory: memory.to_vec(),
+        }
+    }
+
+    /// Runs the program on the computer until it halts, faults, has executed `max_steps`
+    /// instructions or `emit` rejects an output value. Returns the reason and the number of
+    /// executed instructions. The state of the computer is unspecified after a rejected output.
+    #[inline(always)]
+    fn run(
+        &self,
+        computer: &mut Computer,
+        max_steps: usize,
+        mut emit: impl FnMut(u8) -> bool,
+    ) -> (ExecResult, usize) {
+        let Computer {
+            mut a,
+            mut b,
+            mut c,
+            mut ip,
+        } = *computer;
+        let mut steps = 0;
+        let result = 'run: loop {
+            let Some(block) = self.blocks.get(ip) else {
+                break ExecResult::Halted;
+            };
+            if block.end == End::Fault || max_steps - steps <= block.len {
+                // The interpreter takes care of faults and of a step budget which ends within
+                // the block, where the merged instructions would have to be split again.
+                *computer = Computer { a, b, c, ip };
+                let execution = computer.execute_with_budget(&self.memory, max_steps - steps);
+                steps += execution.steps;
+                let result = match execution.result {
+                    _ if !execution.output.into_iter().all(&mut emit) => ExecResult::MissmatchP2,
+                    ExecResult::StepLimitExceeded { .. } => ExecResult::StepLimitExceeded { steps },
+                    result => result,
+                };
+                return (result, steps);
+            }
+            for &op in &block.ops {
+                let out = match op {
+                    Op::AdvLiteral(shift) => {
+                        a >>= shift;
+                        continue;
+                    }
+                    Op::AdvA => {
+                        a = Computer::shr(a, a);
+                        continue;
+                    }
+                    Op::AdvB => {
+                        a = Computer::shr(a, b);
+                        continue;
+                    }
+                    Op::AdvC => {
+                        a = Computer::shr(a, c);
+                        continue;
+                    }
+                    Op::BdvLiteral(shift, xor) => {
+                        b = (a >> shift) ^ xor as u64;
+                        continue;
+                    }
+                    Op::BdvA(xor) => {
+                        b = Computer::shr(a, a) ^ xor as u64;
+                        continue;
+                    }
+                    Op::BdvB(xor) => {
+                        b = Computer::shr(a, b) ^ xor as u64;
+                        continue;
+                    }
+                    Op::BdvC(xor) => {
+                        b = Computer::shr(a, c) ^ xor as u64;
+                        continue;
+                    }
+                    Op::CdvLiteral(shift) => {
+                        c = a >> shift;
+                        continue;
+                    }
+                    Op::CdvA => {
+                        c = Computer::shr(a, a);
+                        continue;
+                    }
+                    Op::CdvB => {
+                        c = Computer::shr(a, b);
+                        continue;
+                    }
+                    Op::CdvC => {
+                        c = Computer::shr(a, c);
+                        continue;
+                    }
+                    Op::SetB(value) => {
+                        b = value as u64;
+                        continue;
+                    }
+                    Op::BstA(xor) => {
+                        b = (a % 8) ^ xor as u64;
+                        continue;
+                    }
+                    Op::BstB(xor) => {
+                        b = (b % 8) ^ xor as u64;
+                        continue;
+                    }
+                    Op::BstC(xor) => {
+                        b = (c % 8) ^ xor as u64;
+                        continue;
+                    }
+                    Op::Bxl(xor) => {
+                        b ^= xor as u64;
+                        continue;
+                    }
+                    Op::Bxc(xor) => {
+                        b ^= c ^ xor as u64;
+                        continue;
+                    }
+                    Op::Nop => continue,
+                    Op::OutLiteral(value) => value,
+                    Op::OutA => (a % 8) as u8,
+                    Op::OutB => (b % 8) as u8,
+                    Op::OutC => (c % 8) as u8,
+                };
+                if !emit(out) {
+                    break 'run ExecResult::MissmatchP2;
+                }
+            }
+            ip += 2 * block.len;
+            steps += block.len;
+            if let End::Jnz(target) = block.end {
+                steps += 1;
+                ip = if a != 0 { target } else { ip + 2 };
+            }
+        };
+        *computer = Computer { a, b, c, ip };
+        (result, steps)
+    }
+
+    /// Same as [Computer::execute_with_budget].
+    pub fn execute_with_budget(&self, computer: &mut Computer, max_steps: usize) -> Execution {
+        let mut output = Vec::new();
+        let (result, steps) = self.run(computer, max_steps, |value| {
+            output.push(value);
+            true
+        });
+        Execution {
+            output,
+            result,
+            steps,
+        }
+    }
+
+    /// Same as [Computer::execute].
+    pub fn execute(
+        &self,
+        computer: &mut Computer,
+        max_steps: usize,
+    ) -> Result<Vec<u8>, ExecResult> {
+        let execution = self.execute_with_budget(computer, max_steps);
+        match execution.result {
+            ExecResult::Halted => Ok(execution.output),
+            fault => Err(fault),
+        }
+    }
+
+    /// Runs the program and returns whether it outputs itself. The run stops at the first output
+    /// which differs from the program. Faults and exceeding the step limit are reported as
+    /// [None].
+    #[inline]
+    pub fn outputs_itself(&self, mut computer: Computer, max_steps: usize) -> Option<bool> {
+        let mut expected = self.memory.iter();
+        let (result, _) = self.run(&mut computer, max_steps, |value| {
+            expected.next() == Some(&value)
+        });
+        match result {
+            ExecResult::Halted => Some(expected.next().is_none()),
+            ExecResult::MissmatchP2 => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assemble;
+
+    #[test]
+    fn same_as_interpreter() {
+        let programs = [
+            vec![2, 4, 1, 5, 7, 5, 1, 6, 4, 1, 5, 5, 0, 3, 3, 0],
+            assemble("start: adv 1\nout a\njnz start").unwrap(),
+            assemble("bst a\ncdv b\nbxc\nbdv 3\nout b\nout c\nadv b\njnz 0").unwrap(),
+            // Jumps to itself, into the middle of an instruction and past the end.
+            vec![3, 0, 0, 1, 3, 7, 0, 5, 4, 3, 2, 3, 20],
+            vec![5, 4, 3, 20],
+        ];
+        for program in &programs {
+            let compiled = CompiledProgram::new(program);
+            for a in [0, 1, 7, 2024, 117440, u64::MAX] {
+                for max_steps in [0, 3, 1000] {
+                    let mut expected = Computer::new(a, 3, 5);
+                    let mut actual = expected;
+                    assert_eq!(
+                        compiled.execute_with_budget(&mut actual, max_steps),
+                        expected.execute_with_budget(program, max_steps),
+                        "program {:?}, a = {}, {} steps",
+                        program,
+                        a,
+                        max_steps
+                    );
+                    assert_eq!(actual, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn merged_instructions() {
+        let compiled = CompiledProgram::new(&[2, 4, 1, 5, 7, 5, 1, 6, 4, 1, 5, 5, 0, 3, 3, 0]);
+        assert_eq!(
+            compiled.blocks[0],
+            Block {
+                ops: vec![
+                    Op::BstA(5),
+                    Op::CdvB,
+                    Op::Bxc(6),
+                    Op::OutB,
+                    Op::AdvLiteral(3)
+                ],
+                len: 7,
+                end: End::Jnz(0),
+            }
+        );
+    }
+
+    #[test]
+    fn faults() {
+        for program in [vec![5, 7], vec![9, 0], vec![5, 1, 2]] {
+            let mut expected = Computer::new(1, 0, 0);
+            let mut actual = expected;
+            assert_eq!(
+                CompiledProgram::new(&program).execute_with_budget(&mut actual, 10),
+                expected.execute_with_budget(&program, 10)
+            );
+            let fault = CompiledProgram::new(&program).execute(&mut Computer::new(1, 0, 0), 10);
+            assert!(fault.as_ref().is_err_and(|fault| fault.is_fault()));
+            assert_eq!(fault, Computer::new(1, 0, 0).execute(&program, 10));
+        }
+    }
+
+    #[test]
+    fn quine() {
+        let compiled = CompiledProgram::new(&[0, 3, 5, 4, 3, 0]);
+        assert_eq!(
+            compiled.outputs_itself(Computer::new(117440, 0, 0), 1000),
+            Some(true)
+        );
+        assert_eq!(
+            compiled.outputs_itself(Computer::new(117448, 0, 0), 1000),
+            Some(false)
+        );
+        assert_eq!(
+            compiled.outputs_itself(Computer::new(3, 0, 0), 1000),
+            Some(false)
+        );
+    }
+}
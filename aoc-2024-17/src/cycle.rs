@@ -0,0 +1,136 @@
+//! Loop detection for the three-bit computer. The next state and output depend only on the
+//! registers and the instruction pointer, so a program which reaches the same [Computer] state
+//! twice repeats the steps in between forever.
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{Computer, ExecResult, Execution};
+
+/// Steps between two visits of the same state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps before the cycle is entered.
+    pub start: usize,
+    /// Number of steps of one pass through the cycle.
+    pub length: usize,
+    /// State at the start of every pass.
+    pub state: Computer,
+    /// Output before the cycle is entered.
+    pub prefix: Vec<u8>,
+    /// Output of every pass, which may be empty.
+    pub output: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Behavior {
+    /// The program halted, faulted or exceeded the step budget before a state repeated.
+    Terminated(Execution),
+    /// The program loops forever.
+    Periodic(Cycle),
+}
+
+impl Display for Behavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Behavior::Terminated(Execution {
+                result: result @ ExecResult::StepLimitExceeded { .. },
+                ..
+            }) => write!(f, "{}", result),
+            Behavior::Terminated(execution) => {
+                write!(f, "{} after {} steps", execution.result, execution.steps)
+            }
+            Behavior::Periodic(cycle) => write!(
+                f,
+                "loops every {} steps after {} steps, outputting {:?} per cycle",
+                cycle.length, cycle.start, cycle.output
+            ),
+        }
+    }
+}
+
+/// Runs the program like [Computer::execute_with_budget], but stops as soon as a state repeats.
+/// Every visited state is recorded, so the memory use grows with the number of steps.
+pub fn classify(mut computer: Computer, memory: &[u8], max_steps: usize) -> Behavior {
+    // Step and output length at the first visit of each state.
+    let mut visited = HashMap::new();
+    let mut output = Vec::new();
+    let mut steps = 0;
+    loop {
+        if let Some(&(start, prefix_len)) = visited.get(&computer) {
+            let cycle_output = output.split_off(prefix_len);
+            return Behavior::Periodic(Cycle {
+                start,
+                length: steps - start,
+                state: computer,
+                prefix: output,
+                output: cycle_output,
+            });
+        }
+        if steps == max_steps && computer.ip < memory.len() {
+            return Behavior::Terminated(Execution {
+                output,
+                result: ExecResult::StepLimitExceeded { steps },
+                steps,
+            });
+        }
+        visited.insert(computer, (steps, output.len()));
+        match computer.execute_next_instruction(memory, &mut output) {
+            ExecResult::Ok => steps += 1,
+            result => {
+                return Behavior::Terminated(Execution {
+                    output,
+                    result,
+                    steps,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asm::assemble, RUN_STEPS};
+
+    #[test]
+    fn periodic_program() {
+        let memory = assemble("out 3\nl: bxl 1\nout b\njnz l").unwrap();
+        let behavior = classify(Computer::new(1, 0, 0), &memory, 1000);
+        assert_eq!(
+            behavior,
+            Behavior::Periodic(Cycle {
+                start: 1,
+                length: 6,
+                state: Computer::new_at_state(1, 0, 0, 2),
+                prefix: vec![3],
+                output: vec![1, 0],
+            })
+        );
+        assert_eq!(
+            behavior.to_string(),
+            "loops every 6 steps after 1 steps, outputting [1, 0] per cycle"
+        );
+    }
+
+    #[test]
+    fn terminating_programs() {
+        let memory = [2, 4, 1, 5, 7, 5, 1, 6, 4, 1, 5, 5, 0, 3, 3, 0];
+        let Behavior::Terminated(execution) = classify(Computer::new(2024, 0, 0), &memory, 1000)
+        else {
+            panic!("the puzzle program halts");
+        };
+        assert_eq!(execution.result, ExecResult::Halted);
+        assert_eq!(
+            execution.output,
+            Computer::new(2024, 0, 0)
+                .execute(&memory, RUN_STEPS)
+                .unwrap()
+        );
+
+        // Halving A until it is zero takes more steps than the budget, without repeating a state.
+        let memory = assemble("l: adv 1\njnz l").unwrap();
+        assert_eq!(
+            classify(Computer::new(u64::MAX, 0, 0), &memory, 10).to_string(),
+            "no halt within 10 steps"
+        );
+    }
+}
@@ -0,0 +1,397 @@
+//! Step debugger for the three-bit computer. The debugger executes a program one instruction at
+//! a time, stops at breakpoints, on changes of watched registers and after a budget of steps, and
+//! records every executed instruction in a [Trace]. A trace can be inspected step by step in both directions with
+//! [Replay], or checked against a fresh execution with [Trace::verify].
+use std::fmt::Display;
+
+use crate::{decode, disasm::annotation, Computer, ExecResult, Instruction, Operand, RUN_STEPS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+
+impl Register {
+    pub fn value(self, computer: &Computer) -> u64 {
+        match self {
+            Register::A => computer.a,
+            Register::B => computer.b,
+            Register::C => computer.c,
+        }
+    }
+}
+
+/// Condition which stops [Debugger::run] before the instruction is executed.
+pub enum Breakpoint {
+    /// Stops when the instruction pointer reaches the address.
+    Address(usize),
+    /// Stops when the condition holds for the registers and the instruction pointer.
+    When(Box<dyn Fn(&Computer) -> bool>),
+}
+
+impl Breakpoint {
+    pub fn when(condition: impl Fn(&Computer) -> bool + 'static) -> Self {
+        Breakpoint::When(Box::new(condition))
+    }
+
+    fn hit(&self, computer: &Computer) -> bool {
+        match self {
+            Breakpoint::Address(address) => computer.ip == *address,
+            Breakpoint::When(condition) => condition(computer),
+        }
+    }
+}
+
+/// One executed instruction and the state of the computer after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceEntry {
+    /// Address of the executed instruction.
+    pub ip: usize,
+    pub instruction: Instruction,
+    pub operand: Operand,
+    pub after: Computer,
+    pub output: Option<u8>,
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = format!("{} {}", self.instruction.mnemonic(), self.operand);
+        write!(
+            f,
+            "{:>3}: {:<6} a={} b={} c={}",
+            self.ip, code, self.after.a, self.after.b, self.after.c
+        )?;
+        if let Some(out) = self.output {
+            write!(f, " out={}", out)?;
+        }
+        Ok(())
+    }
+}
+
+/// All executed instructions of a program run, starting from the initial state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub initial: Computer,
+    pub entries: Vec<TraceEntry>,
+}
+
+/// First step at which a re-execution deviates from a recorded trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub step: usize,
+    pub expected: TraceEntry,
+    /// Executed instruction, or [None] if the program could not execute another instruction.
+    pub actual: Option<TraceEntry>,
+}
+
+impl Trace {
+    /// State of the computer after the given number of steps.
+    pub fn state_at(&self, step: usize) -> Computer {
+        match step {
+            0 => self.initial,
+            _ => self.entries[step - 1].after,
+        }
+    }
+
+    /// Output which was produced within the given number of steps.
+    pub fn output_at(&self, step: usize) -> Vec<u8> {
+        self.entries[..step]
+            .iter()
+            .filter_map(|entry| entry.output)
+            .collect()
+    }
+
+    /// Executes the program again from the initial state and checks that every recorded step
+    /// matches, for example after changes to the computer.
+    pub fn verify(&self, memory: &[u8]) -> Result<(), Divergence> {
+        let mut debugger = Debugger::new(self.initial, memory);
+        for (step, expected) in self.entries.iter().enumerate() {
+            let actual = match debugger.step() {
+                StopReason::Step | StopReason::Watchpoint { .. } => debugger.trace.entries.last(),
+                _ => None,
+            };
+            if actual != Some(expected) {
+                return Err(Divergence {
+                    step,
+                    expected: *expected,
+                    actual: actual.copied(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+/// Walks through a recorded trace without executing the program.
+pub struct Replay<'a> {
+    trace: &'a Trace,
+    step: usize,
+}
+
+impl<'a> Replay<'a> {
+    pub fn new(trace: &'a Trace) -> Self {
+        Self { trace, step: 0 }
+    }
+
+    /// Number of steps which were replayed so far.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn state(&self) -> Computer {
+        self.trace.state_at(self.step)
+    }
+
+    /// Replays the next step, or returns [None] at the end of the trace.
+    pub fn forward(&mut self) -> Option<&'a TraceEntry> {
+        let entry = self.trace.entries.get(self.step)?;
+        self.step += 1;
+        Some(entry)
+    }
+
+    /// Undoes the last replayed step, or returns [None] at the start of the trace.
+    pub fn back(&mut self) -> Option<&'a TraceEntry> {
+        self.step = self.step.checked_sub(1)?;
+        Some(&self.trace.entries[self.step])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// A single step was executed.
+    Step,
+    Halted,
+    /// The breakpoint with this index was hit before the next instruction.
+    Breakpoint(usize),
+    Watchpoint {
+        register: Register,
+        old: u64,
+        new: u64,
+    },
+    /// The next instruction can not be executed. The computer is left unchanged.
+    Fault(ExecResult),
+    /// [Debugger::run] executed its budget of instructions without stopping otherwise.
+    StepLimitExceeded {
+        steps: usize,
+    },
+}
+
+pub struct Debugger<'a> {
+    pub computer: Computer,
+    memory: &'a [u8],
+    pub output: Vec<u8>,
+    pub trace: Trace,
+    /// Instructions a single call to [Debugger::run] may execute, [RUN_STEPS] by default.
+    pub max_steps: usize,
+    breakpoints: Vec<Breakpoint>,
+    watchpoints: Vec<Register>,
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(computer: Computer, memory: &'a [u8]) -> Self {
+        Self {
+            computer,
+            memory,
+            output: Vec::new(),
+            trace: Trace {
+                initial: computer,
+                entries: Vec::new(),
+            },
+            max_steps: RUN_STEPS,
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+        }
+    }
+
+    /// Adds a breakpoint and returns its index, which is reported when it is hit.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        self.breakpoints.push(breakpoint);
+        self.breakpoints.len() - 1
+    }
+
+    /// Stops execution after every instruction which changes the register.
+    pub fn watch(&mut self, register: Register) {
+        self.watchpoints.push(register);
+    }
+
+    /// Annotated listing line of the next instruction, if there is one.
+    pub fn next_instruction(&self) -> Option<String> {
+        if self.computer.ip >= self.memory.len() {
+            return None;
+        }
+        Some(match decode(self.memory, self.computer.ip) {
+            Ok(decoded) => format!(
+                "{} {} ; {}",
+                decoded.instruction.mnemonic(),
+                decoded.operand,
+                annotation(&decoded, self.memory.len())
+            ),
+            Err(e) => e.to_string(),
+        })
+    }
+
+    /// Executes the next instruction. Breakpoints are ignored, but watchpoints are reported.
+    pub fn step(&mut self) -> StopReason {
+        let ip = self.computer.ip;
+        if ip >= self.memory.len() {
+            return StopReason::Halted;
+        }
+        let before = self.computer;
+        let output_len = self.output.len();
+        let result = self
+            .computer
+            .execute_next_instruction(self.memory, &mut self.output);
+        if result.is_fault() {
+            return StopReason::Fault(result);
+        }
+        let decoded = decode(self.memory, ip).expect("executed instructions decode");
+        self.trace.entries.push(TraceEntry {
+            ip,
+            instruction: decoded.instruction,
+            operand: decoded.operand,
+            after: self.computer,
+            output: self.output.get(output_len).copied(),
+        });
+        for &register in &self.watchpoints {
+            let (old, new) = (register.value(&before), register.value(&self.computer));
+            if old != new {
+                return StopReason::Watchpoint { register, old, new };
+            }
+        }
+        StopReason::Step
+    }
+
+    /// Executes instructions until the program halts, a watched register changes, a breakpoint
+    /// is hit or [max_steps](Debugger::max_steps) instructions were executed. Breakpoints are
+    /// checked before each instruction except the first one, so `run` can be called again to
+    /// continue from a breakpoint or after the step limit.
+    pub fn run(&mut self) -> StopReason {
+        for steps in 0..self.max_steps {
+            if steps > 0 {
+                if let Some(idx) = self.breakpoints.iter().position(|b| b.hit(&self.computer)) {
+                    return StopReason::Breakpoint(idx);
+                }
+            }
+            match self.step() {
+                StopReason::Step => (),
+                reason => return reason,
+            }
+        }
+        StopReason::StepLimitExceeded {
+            steps: self.max_steps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assemble;
+
+    fn countdown() -> Vec<u8> {
+        assemble("start: adv 1\nout a\njnz start").unwrap()
+    }
+
+    #[test]
+    fn breakpoints_and_watchpoints() {
+        let memory = countdown();
+        let mut debugger = Debugger::new(Computer::new(20, 0, 0), &memory);
+        let out_bp = debugger.add_breakpoint(Breakpoint::Address(2));
+        let a_bp = debugger.add_breakpoint(Breakpoint::when(|computer| {
+            computer.a < 3 && computer.ip == 4
+        }));
+        assert_eq!(debugger.step(), StopReason::Step);
+        assert_eq!(debugger.computer.a, 10);
+        assert_eq!(debugger.next_instruction().unwrap(), "out a ; output a % 8");
+        assert_eq!(debugger.run(), StopReason::Breakpoint(out_bp));
+        assert_eq!(debugger.computer.a, 5);
+        assert_eq!(debugger.run(), StopReason::Breakpoint(out_bp));
+        assert_eq!(debugger.run(), StopReason::Breakpoint(a_bp));
+        assert_eq!(debugger.computer.a, 2);
+        debugger.watch(Register::A);
+        assert_eq!(
+            debugger.run(),
+            StopReason::Watchpoint {
+                register: Register::A,
+                old: 2,
+                new: 1
+            }
+        );
+        assert_eq!(debugger.output, &[2, 5, 2]);
+    }
+
+    #[test]
+    fn trace_and_replay() {
+        let memory = countdown();
+        let mut debugger = Debugger::new(Computer::new(4, 0, 0), &memory);
+        assert_eq!(debugger.run(), StopReason::Halted);
+        assert_eq!(debugger.output, &[2, 1, 0]);
+        let trace = debugger.trace;
+        assert_eq!(trace.entries.len(), 9);
+        assert_eq!(
+            trace.entries[1].to_string(),
+            "  2: out a  a=2 b=0 c=0 out=2"
+        );
+        assert_eq!(trace.output_at(5), &[2, 1]);
+        assert_eq!(trace.verify(&memory), Ok(()));
+
+        let mut replay = Replay::new(&trace);
+        assert!(replay.back().is_none());
+        replay.forward();
+        replay.forward();
+        assert_eq!(replay.state().ip, 4);
+        assert_eq!(replay.back().unwrap().ip, 2);
+        assert_eq!(replay.state(), trace.state_at(1));
+
+        // The trace of another program diverges when the output changes.
+        let other = assemble("start: adv 1\nout 1\njnz start").unwrap();
+        let divergence = trace.verify(&other).unwrap_err();
+        assert_eq!(divergence.step, 1);
+        assert_eq!(divergence.actual.unwrap().output, Some(1));
+    }
+
+    #[test]
+    fn step_limit() {
+        let memory = assemble("l: bxl 1\njnz l").unwrap();
+        let mut debugger = Debugger::new(Computer::new(1, 0, 0), &memory);
+        debugger.max_steps = 100;
+        assert_eq!(debugger.run(), StopReason::StepLimitExceeded { steps: 100 });
+        assert_eq!(debugger.trace.entries.len(), 100);
+        assert_eq!(debugger.run(), StopReason::StepLimitExceeded { steps: 100 });
+        assert_eq!(debugger.trace.entries.len(), 200);
+        assert_eq!(debugger.computer.ip, 0);
+    }
+
+    #[test]
+    fn invalid_instruction() {
+        let memory = [0, 1, 9, 0];
+        let mut debugger = Debugger::new(Computer::new(8, 0, 0), &memory);
+        assert_eq!(
+            debugger.run(),
+            StopReason::Fault(ExecResult::InvalidOpcode {
+                address: 2,
+                opcode: 9
+            })
+        );
+        assert_eq!(debugger.computer.a, 4);
+        assert_eq!(debugger.trace.entries.len(), 1);
+
+        let memory = [5, 7];
+        let mut debugger = Debugger::new(Computer::new(8, 0, 0), &memory);
+        assert_eq!(
+            debugger.step(),
+            StopReason::Fault(ExecResult::ReservedOperand { address: 0 })
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Disassembler which turns the program memory into a listing of mnemonics. Every instruction is
+//! annotated with its address and its effect on the registers, and jump targets get a label.
+//! Memory which does not decode to a valid instruction is written as a `.byte` directive with the
+//! reason as comment, so the listing of any program memory of 3-bit values is valid input for the
+//! [assembler](crate::asm::assemble) and a disassembled program can be edited and assembled again.
+use std::fmt::Display;
+
+use crate::{decode, ComboOperand, DecodeError, DecodedInstruction, Instruction, Operand};
+
+pub struct Disassembly {
+    /// One entry for every two memory values, in the order of the addresses.
+    pub entries: Vec<Result<DecodedInstruction, DecodeError>>,
+    /// Addresses of instructions which are the target of a `jnz`, sorted. Targets in the middle
+    /// of an instruction or outside of the program are not included.
+    pub jump_targets: Vec<usize>,
+    pub program_len: usize,
+    memory: Vec<u8>,
+}
+
+impl Disassembly {
+    pub fn new(memory: &[u8]) -> Self {
+        let entries: Vec<_> = (0..memory.len())
+            .step_by(2)
+            .map(|address| decode(memory, address))
+            .collect();
+        let mut jump_targets: Vec<usize> = entries
+            .iter()
+            .filter_map(|entry| match entry {
+                Ok(DecodedInstruction {
+                    instruction: Instruction::Jnz,
+                    operand: Operand::Literal(target),
+                    ..
+                }) => Some(*target as usize),
+                _ => None,
+            })
+            .filter(|&target| target % 2 == 0 && target < memory.len())
+            .collect();
+        jump_targets.sort();
+        jump_targets.dedup();
+        Self {
+            entries,
+            jump_targets,
+            program_len: memory.len(),
+            memory: memory.to_vec(),
+        }
+    }
+
+    /// Label of a jump target, numbered in the order of the addresses.
+    pub fn label(&self, address: usize) -> Option<String> {
+        self.jump_targets
+            .binary_search(&address)
+            .ok()
+            .map(|idx| format!("l{}", idx))
+    }
+
+    fn operand_text(&self, decoded: &DecodedInstruction) -> String {
+        match (decoded.instruction, decoded.operand) {
+            (Instruction::Jnz, Operand::Literal(target)) => self
+                .label(target as usize)
+                .unwrap_or_else(|| target.to_string()),
+            (_, operand) => operand.to_string(),
+        }
+    }
+
+    /// The memory at an address which does not hold a valid instruction, as a `.byte` directive.
+    fn bytes_text(&self, address: usize) -> String {
+        let end = (address + 2).min(self.memory.len());
+        let values: Vec<_> = self.memory[address..end]
+            .iter()
+            .map(u8::to_string)
+            .collect();
+        format!(".byte {}", values.join(", "))
+    }
+}
+
+fn modulo(combo: ComboOperand) -> String {
+    match combo {
+        ComboOperand::Literal(value) => value.to_string(),
+        register => format!("{} % 8", register),
+    }
+}
+
+/// Effect of the instruction written as pseudo code.
+pub fn annotation(decoded: &DecodedInstruction, program_len: usize) -> String {
+    if decoded.operand == Operand::Combo(ComboOperand::Reserved) {
+        return "invalid: combo operand 7 is reserved".to_string();
+    }
+    match (decoded.instruction, decoded.operand) {
+        (Instruction::Adv, Operand::Combo(combo)) => format!("a = a >> {}", combo),
+        (Instruction::Bdv, Operand::Combo(combo)) => format!("b = a >> {}", combo),
+        (Instruction::Cdv, Operand::Combo(combo)) => format!("c = a >> {}", combo),
+        (Instruction::Bst, Operand::Combo(combo)) => format!("b = {}", modulo(combo)),
+        (Instruction::Out, Operand::Combo(combo)) => format!("output {}", modulo(combo)),
+        (Instruction::Bxl, Operand::Literal(value)) => format!("b = b ^ {}", value),
+        (Instruction::Bxc, _) => "b = b ^ c".to_string(),
+        (Instruction::Jnz, Operand::Literal(target)) => {
+            let target = target as usize;
+            if target == decoded.address {
+                "no effect, a jump to itself is ignored".to_string()
+            } else if target >= program_len {
+                "if a != 0 jump past the end and halt".to_string()
+            } else if target % 2 == 1 {
+                format!(
+                    "if a != 0 jump to {}, into the middle of an instruction",
+                    target
+                )
+            } else {
+                format!("if a != 0 jump to {}", target)
+            }
+        }
+        _ => unreachable!("operand kind is determined by the instruction"),
+    }
+}
+
+impl Display for Disassembly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            let address = match entry {
+                Ok(decoded) => decoded.address,
+                Err(e) => e.address(),
+            };
+            if let Some(label) = self.label(address) {
+                writeln!(f, "{}:", label)?;
+            }
+            let (code, comment) = match entry {
+                Ok(decoded) if decoded.operand == Operand::Combo(ComboOperand::Reserved) => (
+                    self.bytes_text(address),
+                    annotation(decoded, self.program_len),
+                ),
+                Ok(decoded) => (
+                    format!(
+                        "{} {}",
+                        decoded.instruction.mnemonic(),
+                        self.operand_text(decoded)
+                    ),
+                    annotation(decoded, self.program_len),
+                ),
+                Err(e) => (self.bytes_text(address), e.to_string()),
+            };
+            writeln!(f, "    {:<10} ; {:>2}: {}", code, address, comment)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzle_program() {
+        let program = [2, 4, 1, 5, 7, 5, 1, 6, 4, 1, 5, 5, 0, 3, 3, 0];
+        let disassembly = Disassembly::new(&program);
+        assert_eq!(disassembly.jump_targets, vec![0]);
+        assert_eq!(
+            disassembly.to_string(),
+            "l0:
+    bst a      ;  0: b = a % 8
+    bxl 5      ;  2: b = b ^ 5
+    cdv b      ;  4: c = a >> b
+    bxl 6      ;  6: b = b ^ 6
+    bxc 1      ;  8: b = b ^ c
+    out b      ; 10: output b % 8
+    adv 3      ; 12: a = a >> 3
+    jnz l0     ; 14: if a != 0 jump to 0
+"
+        );
+    }
+
+    #[test]
+    fn invalid_programs() {
+        let disassembly = Disassembly::new(&[5, 7, 6, 7, 3, 1, 2]);
+        assert!(disassembly.jump_targets.is_empty());
+        assert_eq!(
+            disassembly.to_string(),
+            "    .byte 5, 7 ;  0: invalid: combo operand 7 is reserved
+    .byte 6, 7 ;  2: invalid: combo operand 7 is reserved
+    jnz 1      ;  4: if a != 0 jump to 1, into the middle of an instruction
+    .byte 2    ;  6: opcode 2 at address 6 has no operand
+"
+        );
+
+        // Jumps to undecodable memory are labelled as well.
+        let disassembly = Disassembly::new(&[0, 7, 3, 0]);
+        assert_eq!(
+            disassembly.to_string(),
+            "l0:
+    .byte 0, 7 ;  0: invalid: combo operand 7 is reserved
+    jnz l0     ;  2: if a != 0 jump to 0
+"
+        );
+    }
+}
@@ -0,0 +1,680 @@
+use std::fmt::Display;
+
+use aoc_common::{input_lines, Answer, InputMeta, ParseError, Solver};
+
+pub mod asm;
+pub mod compile;
+pub mod cycle;
+pub mod debugger;
+pub mod disasm;
+pub mod quine;
+pub mod symbolic;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, num_enum::TryFromPrimitive)]
+#[repr(u8)]
+pub enum Instruction {
+    Adv = 0,
+    Bxl = 1,
+    Bst = 2,
+    Jnz = 3,
+    Bxc = 4,
+    Out = 5,
+    Bdv = 6,
+    Cdv = 7,
+}
+
+impl Instruction {
+    pub const ALL: [Instruction; 8] = [
+        Instruction::Adv,
+        Instruction::Bxl,
+        Instruction::Bst,
+        Instruction::Jnz,
+        Instruction::Bxc,
+        Instruction::Out,
+        Instruction::Bdv,
+        Instruction::Cdv,
+    ];
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Instruction::Adv => "adv",
+            Instruction::Bxl => "bxl",
+            Instruction::Bst => "bst",
+            Instruction::Jnz => "jnz",
+            Instruction::Bxc => "bxc",
+            Instruction::Out => "out",
+            Instruction::Bdv => "bdv",
+            Instruction::Cdv => "cdv",
+        }
+    }
+
+    /// Interprets the raw operand according to the operand kind of the instruction.
+    pub fn operand(self, op: u8) -> Operand {
+        match self {
+            Instruction::Adv
+            | Instruction::Bst
+            | Instruction::Out
+            | Instruction::Bdv
+            | Instruction::Cdv => Operand::Combo(ComboOperand::from(op)),
+            Instruction::Bxl | Instruction::Jnz => Operand::Literal(op),
+            Instruction::Bxc => Operand::Ignored(op),
+        }
+    }
+}
+
+/// Combo operand, which is either a literal value or the value of a register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComboOperand {
+    Literal(u8),
+    A,
+    B,
+    C,
+    /// Combo operand 7 is reserved and does not appear in valid programs.
+    Reserved,
+}
+
+impl From<u8> for ComboOperand {
+    fn from(op: u8) -> Self {
+        match op {
+            0..=3 => ComboOperand::Literal(op),
+            4 => ComboOperand::A,
+            5 => ComboOperand::B,
+            6 => ComboOperand::C,
+            _ => ComboOperand::Reserved,
+        }
+    }
+}
+
+impl Display for ComboOperand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComboOperand::Literal(value) => write!(f, "{}", value),
+            ComboOperand::A => write!(f, "a"),
+            ComboOperand::B => write!(f, "b"),
+            ComboOperand::C => write!(f, "c"),
+            ComboOperand::Reserved => write!(f, "7"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Combo(ComboOperand),
+    Literal(u8),
+    /// The operand of `bxc` is read but has no effect.
+    Ignored(u8),
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Combo(combo) => write!(f, "{}", combo),
+            Operand::Literal(value) | Operand::Ignored(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Instruction together with its operand and the address it was decoded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecodedInstruction {
+    pub address: usize,
+    pub instruction: Instruction,
+    pub operand: Operand,
+}
+
+/// Memory contents at an address which can not be decoded into an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    InvalidOpcode {
+        address: usize,
+        opcode: u8,
+    },
+    /// The opcode is the last value of the program, so the operand is missing.
+    TruncatedInstruction {
+        address: usize,
+        opcode: u8,
+    },
+}
+
+impl DecodeError {
+    pub fn address(&self) -> usize {
+        match self {
+            DecodeError::InvalidOpcode { address, .. }
+            | DecodeError::TruncatedInstruction { address, .. } => *address,
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::InvalidOpcode { address, opcode } => {
+                write!(f, "invalid opcode {} at address {}", opcode, address)
+            }
+            DecodeError::TruncatedInstruction { address, opcode } => {
+                write!(f, "opcode {} at address {} has no operand", opcode, address)
+            }
+        }
+    }
+}
+
+/// Decodes the instruction at the address. The address must be inside the memory.
+pub fn decode(memory: &[u8], address: usize) -> Result<DecodedInstruction, DecodeError> {
+    let opcode = memory[address];
+    let instruction = Instruction::try_from(opcode)
+        .map_err(|_| DecodeError::InvalidOpcode { address, opcode })?;
+    let op = *memory
+        .get(address + 1)
+        .ok_or(DecodeError::TruncatedInstruction { address, opcode })?;
+    Ok(DecodedInstruction {
+        address,
+        instruction,
+        operand: instruction.operand(op),
+    })
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Computer {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+
+    /// Instruction pointer
+    pub ip: usize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExecResult {
+    Ok,
+    Halted,
+    IterativeStepP2,
+    MissmatchP2,
+    /// The value at the instruction pointer is not an opcode.
+    InvalidOpcode {
+        address: usize,
+        opcode: u8,
+    },
+    /// The instruction uses the reserved combo operand 7.
+    ReservedOperand {
+        address: usize,
+    },
+    /// The instruction pointer points to the last value of the program, so the operand is
+    /// missing.
+    TruncatedInstruction {
+        address: usize,
+    },
+    /// The program did not halt within the step budget.
+    StepLimitExceeded {
+        steps: usize,
+    },
+}
+
+impl ExecResult {
+    /// The program stopped because of an error instead of halting.
+    pub fn is_fault(&self) -> bool {
+        matches!(
+            self,
+            ExecResult::InvalidOpcode { .. }
+                | ExecResult::ReservedOperand { .. }
+                | ExecResult::TruncatedInstruction { .. }
+                | ExecResult::StepLimitExceeded { .. }
+        )
+    }
+}
+
+impl From<DecodeError> for ExecResult {
+    fn from(e: DecodeError) -> Self {
+        match e {
+            DecodeError::InvalidOpcode { address, opcode } => {
+                ExecResult::InvalidOpcode { address, opcode }
+            }
+            DecodeError::TruncatedInstruction { address, .. } => {
+                ExecResult::TruncatedInstruction { address }
+            }
+        }
+    }
+}
+
+impl Display for ExecResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecResult::Ok => write!(f, "ok"),
+            ExecResult::Halted => write!(f, "halted"),
+            ExecResult::IterativeStepP2 => write!(f, "iterative step"),
+            ExecResult::MissmatchP2 => write!(f, "output does not match the program"),
+            ExecResult::InvalidOpcode { address, opcode } => {
+                write!(f, "invalid opcode {} at address {}", opcode, address)
+            }
+            ExecResult::ReservedOperand { address } => {
+                write!(f, "reserved combo operand 7 at address {}", address)
+            }
+            ExecResult::TruncatedInstruction { address } => {
+                write!(f, "instruction at address {} has no operand", address)
+            }
+            ExecResult::StepLimitExceeded { steps } => {
+                write!(f, "no halt within {} steps", steps)
+            }
+        }
+    }
+}
+
+/// Output of a program run and the reason why the run ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Execution {
+    pub output: Vec<u8>,
+    /// [ExecResult::Halted] or a fault.
+    pub result: ExecResult,
+    pub steps: usize,
+}
+
+impl Computer {
+    pub fn new_from_data(data: &[u8]) -> Result<(Self, Vec<u8>), ParseError> {
+        let mut a = 0;
+        let mut b = 0;
+        let mut c = 0;
+        let mut memory = None;
+        for line in input_lines(data)? {
+            if let Some(value) = line.text.strip_prefix("Register A:") {
+                a = line.parse(value, "a register value")?;
+            } else if let Some(value) = line.text.strip_prefix("Register B:") {
+                b = line.parse(value, "a register value")?;
+            } else if let Some(value) = line.text.strip_prefix("Register C:") {
+                c = line.parse(value, "a register value")?;
+            } else if let Some(program) = line.text.strip_prefix("Program:") {
+                memory = Some(
+                    program
+                        .split(',')
+                        .map(|x| match line.parse::<u8>(x, "a 3-bit number")? {
+                            value @ 0..=7 => Ok(value),
+                            _ => Err(line.expected(x.trim(), "a 3-bit number")),
+                        })
+                        .collect::<Result<Vec<u8>, ParseError>>()?,
+                );
+            } else if !line.text.is_empty() {
+                return Err(line.error(1, "expected a register or the program"));
+            }
+        }
+        let memory = memory.ok_or_else(|| ParseError::missing("program", "input"))?;
+        Ok((Self::new(a, b, c), memory))
+    }
+
+    pub fn new_at_state(a: u64, b: u64, c: u64, ip: usize) -> Computer {
+        Computer { a, b, c, ip }
+    }
+
+    pub fn new(a: u64, b: u64, c: u64) -> Computer {
+        Computer::new_at_state(a, b, c, 0)
+    }
+
+    /// Runs the program until it halts and returns its output. Faults and exceeding `max_steps`
+    /// instructions are errors, see [Self::execute_with_budget] for the output up to them.
+    pub fn execute(&mut self, memory: &[u8], max_steps: usize) -> Result<Vec<u8>, ExecResult> {
+        let execution = self.execute_with_budget(memory, max_steps);
+        match execution.result {
+            ExecResult::Halted => Ok(execution.output),
+            fault => Err(fault),
+        }
+    }
+
+    /// Runs the program until it halts, faults or has executed `max_steps` instructions.
+    pub fn execute_with_budget(&mut self, memory: &[u8], max_steps: usize) -> Execution {
+        let mut output = Vec::new();
+        let mut steps = 0;
+        loop {
+            if steps == max_steps && self.ip < memory.len() {
+                return Execution {
+                    output,
+                    result: ExecResult::StepLimitExceeded { steps },
+                    steps,
+                };
+            }
+            match self.execute_next_instruction(memory, &mut output) {
+                ExecResult::Ok => steps += 1,
+                result => {
+                    return Execution {
+                        output,
+                        result,
+                        steps,
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn execute_p2_brute_force(&mut self, a: u64, memory: &[u8]) -> bool {
+        self.reset();
+        self.a = a;
+        let mut output = Vec::new();
+        let mut state_chain = Vec::new();
+        while let ExecResult::Ok =
+            self.execute_next_instruction_p2(a, memory, &mut state_chain, &mut output)
+        {}
+        output == memory
+    }
+
+    pub fn reset(&mut self) {
+        self.a = 0;
+        self.b = 0;
+        self.c = 0;
+        self.ip = 0;
+    }
+
+    pub fn execute_next_instruction_p2(
+        &mut self,
+        _init_a: u64,
+        memory: &[u8],
+        state_chain: &mut Vec<Computer>,
+        out: &mut Vec<u8>,
+    ) -> ExecResult {
+        if self.ip >= memory.len() {
+            return ExecResult::Halted;
+        }
+        let (next_instruction, next_op) = match self.fetch(memory) {
+            Ok(fetched) => fetched,
+            Err(fault) => return fault,
+        };
+        state_chain.push(*self);
+        let result = match next_instruction {
+            Instruction::Jnz if self.jnz(next_op) => return ExecResult::Ok,
+            Instruction::Out => self.out(next_op).map(|value| out.push(value)),
+            _ => self.apply(next_instruction, next_op),
+        };
+        if let Err(fault) = result {
+            return fault;
+        }
+        if next_instruction == Instruction::Out && out.last() != memory.get(out.len() - 1) {
+            return ExecResult::MissmatchP2;
+        }
+        self.ip += 2;
+        ExecResult::Ok
+    }
+
+    /// Decodes the instruction at the instruction pointer, which must be inside the memory.
+    fn fetch(&self, memory: &[u8]) -> Result<(Instruction, u8), ExecResult> {
+        let decoded = decode(memory, self.ip)?;
+        if decoded.operand == Operand::Combo(ComboOperand::Reserved) {
+            return Err(ExecResult::ReservedOperand { address: self.ip });
+        }
+        Ok((decoded.instruction, memory[self.ip + 1]))
+    }
+
+    /// Executes one instruction. Invalid instructions are reported as faults and leave the
+    /// computer unchanged.
+    pub fn execute_next_instruction(&mut self, memory: &[u8], out: &mut Vec<u8>) -> ExecResult {
+        if self.ip >= memory.len() {
+            return ExecResult::Halted;
+        }
+        let (next_instruction, next_op) = match self.fetch(memory) {
+            Ok(fetched) => fetched,
+            Err(fault) => return fault,
+        };
+        let result = match next_instruction {
+            Instruction::Jnz if self.jnz(next_op) => return ExecResult::Ok,
+            Instruction::Out => self.out(next_op).map(|value| out.push(value)),
+            _ => self.apply(next_instruction, next_op),
+        };
+        if let Err(fault) = result {
+            return fault;
+        }
+        self.ip += 2;
+        ExecResult::Ok
+    }
+
+    /// Applies the effect of an instruction on the registers. The output of `out` and the jump
+    /// of `jnz` are left to the caller.
+    fn apply(&mut self, instruction: Instruction, op: u8) -> Result<(), ExecResult> {
+        match instruction {
+            Instruction::Adv => return self.adv(op),
+            Instruction::Bxl => self.bxl(op),
+            Instruction::Bst => return self.bst(op),
+            Instruction::Bxc => self.bxc(),
+            Instruction::Bdv => return self.bdv(op),
+            Instruction::Cdv => return self.cdv(op),
+            Instruction::Jnz | Instruction::Out => (),
+        }
+        Ok(())
+    }
+
+    /// Value of a combo operand. The reserved operand 7 is a fault at the instruction pointer.
+    pub fn combo_op(&self, op: u8) -> Result<u64, ExecResult> {
+        match ComboOperand::from(op) {
+            ComboOperand::Literal(value) => Ok(value as u64),
+            ComboOperand::A => Ok(self.a),
+            ComboOperand::B => Ok(self.b),
+            ComboOperand::C => Ok(self.c),
+            ComboOperand::Reserved => Err(ExecResult::ReservedOperand { address: self.ip }),
+        }
+    }
+
+    /// Division of `a` by two to the power of `shift`, which is zero for shifts past the width.
+    #[inline]
+    pub fn shr(a: u64, shift: u64) -> u64 {
+        if shift < u64::BITS as u64 {
+            a >> shift
+        } else {
+            0
+        }
+    }
+
+    pub fn div_op(&self, op: u8) -> Result<u64, ExecResult> {
+        Ok(Self::shr(self.a, self.combo_op(op)?))
+    }
+
+    pub fn adv(&mut self, op: u8) -> Result<(), ExecResult> {
+        self.a = self.div_op(op)?;
+        Ok(())
+    }
+
+    pub fn bxl(&mut self, op: u8) {
+        self.b ^= op as u64
+    }
+
+    pub fn bst(&mut self, op: u8) -> Result<(), ExecResult> {
+        self.b = self.combo_op(op)? % 8;
+        Ok(())
+    }
+
+    pub fn jnz(&mut self, op: u8) -> bool {
+        if self.a == 0 || self.ip == op as usize {
+            return false;
+        }
+        self.ip = op as usize;
+        true
+    }
+
+    pub fn bxc(&mut self) {
+        self.b ^= self.c
+    }
+
+    pub fn out(&mut self, op: u8) -> Result<u8, ExecResult> {
+        Ok((self.combo_op(op)? % 8) as u8)
+    }
+
+    pub fn bdv(&mut self, op: u8) -> Result<(), ExecResult> {
+        self.b = self.div_op(op)?;
+        Ok(())
+    }
+
+    pub fn cdv(&mut self, op: u8) -> Result<(), ExecResult> {
+        self.c = self.div_op(op)?;
+        Ok(())
+    }
+}
+
+pub fn decompiled_program(mut a: u64) -> (u64, u8) {
+    // Program: 2,4,1,5,7,5,1,6,4,1,5,5,0,3,3,0
+    //  1. (2, 4): bst(4): b = a & 7
+    //  2. (1, 5): bxl(5): b = b ^ 5
+    //  3. (7, 5): cdv(5): c = a / (2 pow B) = a >> b
+    //  4. (1, 6): bxl(6): b = b ^ 6
+    //  5. (4, 1): bxc(1): b = b ^ c
+    //  6. (5, 5): out(5): out (b % 8) = out (b & 7)
+    //  7. (0, 3): adv(3): a = a / (2 pow 3) = a >> 3
+    //  8. (3, 0): jnz(0): if a == 0 { nothing } else { ip = 0 }
+
+    // Insights:
+    //  1. The program loops until a is 0. a is always shifted 3 to the right and will eventually
+    //     become 0
+    //  2. The output of the program is the next a value and the output value. b and c and derived.
+    //  3. Derived insight: The last value of the output is generated with a being between 0 and 7.
+    //
+    let mut b = a & 7;
+    b ^= 5;
+    let c = a >> b;
+    b ^= 6;
+    b ^= c;
+    let out = (b & 7) as u8;
+    a >>= 3;
+    (a, out)
+}
+
+/// Joins the program output with commas, which is the expected answer format.
+pub fn format_output(out: &[u8]) -> String {
+    out.iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parsed input of the solver, with the search limits of part 2 from the input metadata.
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub computer: Computer,
+    pub memory: Vec<u8>,
+    pub limits: quine::SearchLimits,
+}
+
+impl Puzzle {
+    pub fn new(data: &[u8], meta: &InputMeta) -> Result<Self, ParseError> {
+        let (computer, memory) = Computer::new_from_data(data)?;
+        Ok(Self {
+            computer,
+            memory,
+            limits: quine::SearchLimits::from_meta(meta)?,
+        })
+    }
+}
+
+/// Instructions a program may execute in part 1 before it is considered not to halt.
+pub const RUN_STEPS: usize = 1 << 24;
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    type Input = Puzzle;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        Puzzle::new(data, &InputMeta::default())
+    }
+
+    fn parse_with_meta(data: &[u8], meta: &InputMeta) -> Result<Self::Input, ParseError> {
+        Puzzle::new(data, meta)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut computer = input.computer;
+        match computer.execute(&input.memory, RUN_STEPS) {
+            Ok(out) => format_output(&out).into(),
+            Err(fault) => Answer::Failed(fault.to_string()),
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        match quine::find_quine(&input.computer, &input.memory, input.limits) {
+            Ok(quine) => quine.a.into(),
+            Err(e) => Answer::Failed(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assemble;
+
+    #[test]
+    fn sample_programs() {
+        let program = |source| assemble(source).unwrap();
+        let mut computer = Computer::new(0, 0, 9);
+        computer.execute(&program("bst c"), RUN_STEPS).unwrap();
+        assert_eq!(computer.b, 1);
+        let mut computer = Computer::new(10, 0, 0);
+        let out = computer.execute(&program("out 0\nout 1\nout a"), RUN_STEPS);
+        assert_eq!(out.as_deref(), Ok(&[0, 1, 2][..]));
+        let mut computer = Computer::new(2024, 0, 0);
+        let out = computer.execute(&program("start: adv 1\nout a\njnz start"), RUN_STEPS);
+        assert_eq!(out.as_deref(), Ok(&[4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0][..]));
+        assert_eq!(computer.a, 0);
+        let mut computer = Computer::new(0, 29, 0);
+        computer.execute(&program("bxl 7"), RUN_STEPS).unwrap();
+        assert_eq!(computer.b, 26);
+        let mut computer = Computer::new(0, 2024, 43690);
+        computer.execute(&program("bxc"), RUN_STEPS).unwrap();
+        assert_eq!(computer.b, 44354);
+    }
+
+    #[test]
+    fn brute_force_quine() {
+        let memory = [0, 3, 5, 4, 3, 0];
+        let mut computer = Computer::new(0, 0, 0);
+        let a = (0..).find(|&a| computer.execute_p2_brute_force(a, &memory));
+        assert_eq!(a, Some(117440));
+    }
+
+    #[test]
+    fn faults() {
+        let run = |memory: &[u8]| Computer::new(1, 0, 0).execute_with_budget(memory, 100);
+        assert_eq!(
+            run(&[5, 7]).result,
+            ExecResult::ReservedOperand { address: 0 }
+        );
+        assert_eq!(
+            run(&[9, 0]).result,
+            ExecResult::InvalidOpcode {
+                address: 0,
+                opcode: 9
+            }
+        );
+        let truncated = run(&[5, 1, 2]);
+        assert_eq!(
+            truncated.result,
+            ExecResult::TruncatedInstruction { address: 2 }
+        );
+        assert_eq!(truncated.output, &[1]);
+        assert_eq!(truncated.steps, 1);
+        assert_eq!(
+            Computer::new(1, 0, 0).execute(&[5, 1, 2], 100),
+            Err(ExecResult::TruncatedInstruction { address: 2 })
+        );
+        assert_eq!(
+            Computer::new(0, 0, 0).combo_op(7),
+            Err(ExecResult::ReservedOperand { address: 0 })
+        );
+
+        let puzzle = Day17::parse(b"Program: 5,7").unwrap();
+        assert_eq!(
+            Day17::part1(&puzzle),
+            Answer::Failed("reserved combo operand 7 at address 0".to_string())
+        );
+    }
+
+    #[test]
+    fn step_budget() {
+        let memory = assemble("l: out 1\njnz l").unwrap();
+        let execution = Computer::new(1, 0, 0).execute_with_budget(&memory, 10);
+        assert_eq!(
+            execution.result,
+            ExecResult::StepLimitExceeded { steps: 10 }
+        );
+        assert_eq!(execution.output, &[1; 5]);
+
+        let execution = Computer::new(0, 0, 0).execute_with_budget(&memory, 10);
+        assert_eq!(execution.result, ExecResult::Halted);
+        assert_eq!(execution.steps, 2);
+        assert_eq!(
+            Computer::new(1, 0, 0).execute(&memory, 10),
+            Err(ExecResult::StepLimitExceeded { steps: 10 })
+        );
+        assert_eq!(Computer::new(0, 0, 0).execute(&memory, 10), Ok(vec![1]));
+    }
+}
@@ -0,0 +1,455 @@
+//! Generic search for the lowest value of register A which makes a program output itself.
+//!
+//! Most puzzle programs are a single loop which ends with `jnz 0`. Each iteration shifts A to
+//! the right by a fixed number of bits with `adv`, outputs one value, and derives B and C from A
+//! before reading them. The output of an iteration only depends on A, so A can be built from its
+//! most significant bits: the last output is produced by the highest bits, and each earlier
+//! output adds the next lower bits. Other loops ending with `jnz 0` are solved by
+//! [symbolic](crate::symbolic) evaluation, and the remaining programs fall back to a bounded
+//! brute-force search. The reason why the shape does not fit is reported.
+use std::fmt::Display;
+
+use aoc_common::{InputMeta, ParseError};
+
+use crate::{
+    compile::CompiledProgram, decode, symbolic, ComboOperand, Computer, DecodeError, Instruction,
+    Operand,
+};
+
+/// Reason why a program does not fit the shape of a shifting loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeMismatch {
+    InvalidInstruction(DecodeError),
+    ReservedOperand {
+        address: usize,
+    },
+    /// The program must end with `jnz 0`.
+    NoFinalJump,
+    ExtraJump {
+        address: usize,
+    },
+    /// The loop must contain exactly one `adv`.
+    ShiftCount(usize),
+    /// The `adv` must shift by a literal from 1 to 3.
+    ShiftOperand {
+        address: usize,
+        operand: ComboOperand,
+    },
+    /// The loop must contain exactly one `out`.
+    OutputCount(usize),
+    /// B or C is read before it is written, so it carries state between iterations.
+    ReadBeforeWrite {
+        address: usize,
+        register: char,
+    },
+}
+
+impl Display for ShapeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShapeMismatch::InvalidInstruction(e) => write!(f, "{}", e),
+            ShapeMismatch::ReservedOperand { address } => {
+                write!(f, "reserved combo operand 7 at address {}", address)
+            }
+            ShapeMismatch::NoFinalJump => write!(f, "the program does not end with jnz 0"),
+            ShapeMismatch::ExtraJump { address } => {
+                write!(f, "jnz at address {} before the end of the loop", address)
+            }
+            ShapeMismatch::ShiftCount(count) => {
+                write!(f, "expected one adv in the loop, found {}", count)
+            }
+            ShapeMismatch::ShiftOperand { address, operand } => write!(
+                f,
+                "adv at address {} shifts by {}, expected a literal from 1 to 3",
+                address, operand
+            ),
+            ShapeMismatch::OutputCount(count) => {
+                write!(f, "expected one out in the loop, found {}", count)
+            }
+            ShapeMismatch::ReadBeforeWrite { address, register } => write!(
+                f,
+                "{} is read at address {} before it is written in the loop",
+                register, address
+            ),
+        }
+    }
+}
+
+fn combo_register(operand: Operand) -> Option<char> {
+    match operand {
+        Operand::Combo(ComboOperand::B) => Some('b'),
+        Operand::Combo(ComboOperand::C) => Some('c'),
+        _ => None,
+    }
+}
+
+/// Checks whether the program is a shifting loop and returns the number of bits A is shifted by
+/// per iteration.
+pub fn detect_shape(memory: &[u8]) -> Result<u8, ShapeMismatch> {
+    let mut instructions = Vec::new();
+    for address in (0..memory.len()).step_by(2) {
+        let decoded = decode(memory, address).map_err(ShapeMismatch::InvalidInstruction)?;
+        if decoded.operand == Operand::Combo(ComboOperand::Reserved) {
+            return Err(ShapeMismatch::ReservedOperand { address });
+        }
+        instructions.push(decoded);
+    }
+    let Some((last, body)) = instructions.split_last() else {
+        return Err(ShapeMismatch::NoFinalJump);
+    };
+    if last.instruction != Instruction::Jnz || last.operand != Operand::Literal(0) {
+        return Err(ShapeMismatch::NoFinalJump);
+    }
+    let mut shifts = Vec::new();
+    let mut outputs = 0;
+    // A is the only register which may be read before it is written.
+    let (mut b_written, mut c_written) = (false, false);
+    for decoded in body {
+        let address = decoded.address;
+        let mut reads = Vec::from_iter(combo_register(decoded.operand));
+        match decoded.instruction {
+            Instruction::Jnz => return Err(ShapeMismatch::ExtraJump { address }),
+            Instruction::Adv => shifts.push(decoded),
+            Instruction::Out => outputs += 1,
+            Instruction::Bxl => reads.push('b'),
+            Instruction::Bxc => reads.extend(['b', 'c']),
+            _ => (),
+        }
+        for register in reads {
+            let written = if register == 'b' {
+                b_written
+            } else {
+                c_written
+            };
+            if !written {
+                return Err(ShapeMismatch::ReadBeforeWrite { address, register });
+            }
+        }
+        match decoded.instruction {
+            Instruction::Bst | Instruction::Bdv => b_written = true,
+            Instruction::Cdv => c_written = true,
+            _ => (),
+        }
+    }
+    let [shift] = shifts[..] else {
+        return Err(ShapeMismatch::ShiftCount(shifts.len()));
+    };
+    if outputs != 1 {
+        return Err(ShapeMismatch::OutputCount(outputs));
+    }
+    match shift.operand {
+        Operand::Combo(ComboOperand::Literal(bits @ 1..=3)) => Ok(bits),
+        Operand::Combo(operand) => Err(ShapeMismatch::ShiftOperand {
+            address: shift.address,
+            operand,
+        }),
+        _ => unreachable!("adv has a combo operand"),
+    }
+}
+
+/// Limits of the searches for programs which do not fit the shape. The defaults keep a search
+/// which finds nothing below a second, and can be raised with the `max_decisions`,
+/// `max_candidates` and `max_steps` parameters of the input metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Bits of A which the symbolic solver assigns before it gives up.
+    pub max_decisions: usize,
+    /// Values of A which are tried, starting at 0.
+    pub max_candidates: u64,
+    /// Instructions executed per candidate before it is given up.
+    pub max_steps: usize,
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self {
+            max_decisions: 100_000,
+            max_candidates: 1 << 16,
+            max_steps: 1_000,
+        }
+    }
+}
+
+impl SearchLimits {
+    pub fn from_meta(meta: &InputMeta) -> Result<Self, ParseError> {
+        let default = Self::default();
+        Ok(Self {
+            max_decisions: meta.get_or("max_decisions", default.max_decisions)?,
+            max_candidates: meta.get_or("max_candidates", default.max_candidates)?,
+            max_steps: meta.get_or("max_steps", default.max_steps)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// A was built from its highest bits for a loop shifting A by this many bits.
+    Shape { shift: u8 },
+    /// A was solved from the symbolic outputs of a loop which does not fit the shape.
+    Symbolic { mismatch: ShapeMismatch },
+    /// A was found by trying all values in order, because the program does not fit the shape.
+    BruteForce { mismatch: ShapeMismatch },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quine {
+    pub a: u64,
+    pub method: Method,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuineError {
+    /// The program fits the shape, but no value of A makes it output itself.
+    NoSolution { shift: u8 },
+    /// The program does not fit the shape, and its symbolic outputs prove that no value of A
+    /// makes it output itself.
+    Unsatisfiable { mismatch: ShapeMismatch },
+    /// The program does not fit the shape, and none of the tried values of A makes it output
+    /// itself.
+    NotFound {
+        mismatch: ShapeMismatch,
+        limits: SearchLimits,
+        /// Candidates which were given up because they exceeded the step limit or faulted.
+        aborted: u64,
+    },
+}
+
+impl Display for QuineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuineError::NoSolution { shift } => write!(
+                f,
+                "the program is a loop shifting A by {} bits, but no value of A outputs the program",
+                shift
+            ),
+            QuineError::Unsatisfiable { mismatch } => write!(
+                f,
+                "the program does not fit the shape of a shifting loop ({}), and symbolic \
+                evaluation shows that no value of A outputs the program",
+                mismatch
+            ),
+            QuineError::NotFound {
+                mismatch,
+                limits,
+                aborted,
+            } => write!(
+                f,
+                "the program does not fit the shape of a shifting loop ({}), and no A below {} \
+                outputs the program ({} candidates exceeded {} steps or faulted)",
+                mismatch, limits.max_candidates, aborted, limits.max_steps
+            ),
+        }
+    }
+}
+
+/// Output of one iteration of the loop body, which is everything but the final `jnz`.
+fn iteration_output(memory: &[u8], a: u64) -> u8 {
+    let mut computer = Computer::new(a, 0, 0);
+    let mut out = Vec::with_capacity(1);
+    while computer.ip < memory.len() - 2 {
+        computer.execute_next_instruction(memory, &mut out);
+    }
+    out[0]
+}
+
+/// Extends the highest bits of A with the bits for the output at `idx` and all earlier outputs.
+/// Candidates are tried in ascending order, so the first complete value is the lowest one.
+fn extend(memory: &[u8], shift: u8, idx: usize, high_bits: u64) -> Option<u64> {
+    for low_bits in 0..1 << shift {
+        let a = (high_bits << shift) | low_bits;
+        // The loop would end one iteration early if the last iteration started with A = 0.
+        if a == 0 || iteration_output(memory, a) != memory[idx] {
+            continue;
+        }
+        if idx == 0 {
+            return Some(a);
+        }
+        if let Some(a) = extend(memory, shift, idx - 1, a) {
+            return Some(a);
+        }
+    }
+    None
+}
+
+/// Finds the lowest value of register A which makes the program output itself. B and C keep the
+/// values of the computer for the symbolic and the brute-force search.
+pub fn find_quine(
+    computer: &Computer,
+    memory: &[u8],
+    limits: SearchLimits,
+) -> Result<Quine, QuineError> {
+    let mismatch = match detect_shape(memory) {
+        Ok(shift) => {
+            return extend(memory, shift, memory.len() - 1, 0)
+                .map(|a| Quine {
+                    a,
+                    method: Method::Shape { shift },
+                })
+                .ok_or(QuineError::NoSolution { shift })
+        }
+        Err(mismatch) => mismatch,
+    };
+    // Unsupported programs and exceeded decision limits fall through to the brute-force search.
+    match symbolic::find_quine(computer, memory, limits.max_decisions) {
+        Ok(Some(a)) => {
+            return Ok(Quine {
+                a,
+                method: Method::Symbolic { mismatch },
+            })
+        }
+        Ok(None) => return Err(QuineError::Unsatisfiable { mismatch }),
+        Err(_) => (),
+    }
+    let compiled = CompiledProgram::new(memory);
+    let mut aborted = 0;
+    for a in 0..limits.max_candidates {
+        let candidate = Computer::new(a, computer.b, computer.c);
+        match compiled.outputs_itself(candidate, limits.max_steps) {
+            Some(true) => {
+                return Ok(Quine {
+                    a,
+                    method: Method::BruteForce { mismatch },
+                })
+            }
+            Some(false) => (),
+            None => aborted += 1,
+        }
+    }
+    Err(QuineError::NotFound {
+        mismatch,
+        limits,
+        aborted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asm::assemble, RUN_STEPS};
+
+    #[test]
+    fn puzzle_programs() {
+        let example = [0, 3, 5, 4, 3, 0];
+        let quine = find_quine(&Computer::new(0, 0, 0), &example, SearchLimits::default());
+        assert_eq!(
+            quine,
+            Ok(Quine {
+                a: 117440,
+                method: Method::Shape { shift: 3 }
+            })
+        );
+        let input = [2, 4, 1, 5, 7, 5, 1, 6, 4, 1, 5, 5, 0, 3, 3, 0];
+        let quine = find_quine(&Computer::new(0, 0, 0), &input, SearchLimits::default()).unwrap();
+        assert_eq!(quine.a, 107413700225434);
+        assert_eq!(
+            Computer::new(quine.a, 0, 0)
+                .execute(&input, RUN_STEPS)
+                .unwrap(),
+            input
+        );
+    }
+
+    #[test]
+    fn shape_mismatches() {
+        let shape = |source| detect_shape(&assemble(source).unwrap());
+        assert_eq!(shape("bst a\nout b\nadv 3\njnz 0"), Ok(3));
+        assert_eq!(shape("adv 2\nout a\njnz 0"), Ok(2));
+        assert_eq!(shape("adv 3\nout a"), Err(ShapeMismatch::NoFinalJump));
+        assert_eq!(
+            shape("adv 3\nout a\nadv 1\njnz 0"),
+            Err(ShapeMismatch::ShiftCount(2))
+        );
+        assert_eq!(
+            shape("adv b\nout a\njnz 0"),
+            Err(ShapeMismatch::ReadBeforeWrite {
+                address: 0,
+                register: 'b'
+            })
+        );
+        assert_eq!(
+            shape("bst a\nadv b\nout a\njnz 0"),
+            Err(ShapeMismatch::ShiftOperand {
+                address: 2,
+                operand: ComboOperand::B
+            })
+        );
+        assert_eq!(
+            shape("bst a\nbxc\nadv 3\nout b\njnz 0"),
+            Err(ShapeMismatch::ReadBeforeWrite {
+                address: 2,
+                register: 'c'
+            })
+        );
+        assert_eq!(shape("adv 3\njnz 0"), Err(ShapeMismatch::OutputCount(0)));
+        assert_eq!(
+            detect_shape(&[0, 3, 5, 7, 3, 0]),
+            Err(ShapeMismatch::ReservedOperand { address: 2 })
+        );
+    }
+
+    #[test]
+    fn symbolic_fallback() {
+        let memory = assemble("adv 3\nout a\nadv 1\njnz 0").unwrap();
+        let quine = find_quine(&Computer::new(0, 0, 0), &memory, SearchLimits::default());
+        assert_eq!(
+            quine,
+            Ok(Quine {
+                a: 411183488,
+                method: Method::Symbolic {
+                    mismatch: ShapeMismatch::ShiftCount(2)
+                }
+            })
+        );
+        assert_eq!(
+            Computer::new(411183488, 0, 0)
+                .execute(&memory, RUN_STEPS)
+                .unwrap(),
+            memory
+        );
+        // A is never shifted because B is 0, so the loop only ends when A starts at 0.
+        let memory = assemble("adv b\nout a\njnz 0").unwrap();
+        assert!(matches!(
+            find_quine(&Computer::new(0, 0, 0), &memory, SearchLimits::default()),
+            Err(QuineError::Unsatisfiable { .. })
+        ));
+    }
+
+    #[test]
+    fn limits_from_meta() {
+        let mut meta = InputMeta::new("example");
+        meta.parse_params("max_candidates = 1000");
+        let limits = SearchLimits::from_meta(&meta).unwrap();
+        assert_eq!(limits.max_candidates, 1000);
+        assert_eq!(limits.max_steps, SearchLimits::default().max_steps);
+        meta.parse_params("max_steps = many");
+        assert!(SearchLimits::from_meta(&meta).is_err());
+    }
+
+    #[test]
+    fn bounded_fallback() {
+        let limits = SearchLimits {
+            max_decisions: 1000,
+            max_candidates: 100,
+            max_steps: 50,
+        };
+        let memory = assemble("out 1\nl: jnz l\njnz 0").unwrap();
+        let error = find_quine(&Computer::new(0, 0, 0), &memory, limits).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the program does not fit the shape of a shifting loop (jnz at address 2 before the \
+            end of the loop), and no A below 100 outputs the program (0 candidates exceeded 50 \
+            steps or faulted)"
+        );
+        // The loop never ends for A != 0 because A is not shifted.
+        let memory = assemble("out b\nl: adv 0\njnz l").unwrap();
+        let error = find_quine(&Computer::new(0, 5, 0), &memory, limits).unwrap_err();
+        assert!(matches!(
+            error,
+            QuineError::NotFound {
+                mismatch: ShapeMismatch::NoFinalJump,
+                aborted: 99,
+                ..
+            }
+        ));
+    }
+}
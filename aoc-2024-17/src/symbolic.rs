@@ -0,0 +1,510 @@
+//! Symbolic execution of loops which end with `jnz 0`. Register A is a vector of 64 unknown bits,
+//! and the loop is unrolled so every register and output value becomes a vector of boolean
+//! expressions over these bits. The constraints for a program which outputs itself are solved by
+//! a backtracking search over the bits of A, which generalises [decompiled_program] to any such
+//! loop.
+//!
+//! [decompiled_program]: crate::decompiled_program
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{decode, ComboOperand, Computer, Instruction, Operand};
+
+/// Boolean expression in [Expressions].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Bit(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Node {
+    Const(bool),
+    /// Bit of the initial value of A.
+    Var(u8),
+    Not(Bit),
+    And(Bit, Bit),
+    Or(Bit, Bit),
+    Xor(Bit, Bit),
+    /// If the first bit is set the second one, otherwise the third one.
+    Ite(Bit, Bit, Bit),
+}
+
+/// Register or output value, lowest bit first.
+pub type Word = [Bit; 64];
+
+/// Boolean expressions over the bits of the initial value of A. Equal expressions are stored
+/// once, and expressions with constant operands are folded.
+pub struct Expressions {
+    /// Every node only refers to nodes before it.
+    nodes: Vec<Node>,
+    ids: HashMap<Node, Bit>,
+}
+
+impl Default for Expressions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Expressions {
+    pub fn new() -> Self {
+        let mut exprs = Self {
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+        };
+        exprs.add(Node::Const(false));
+        exprs.add(Node::Const(true));
+        exprs
+    }
+
+    /// Number of stored expressions.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn add(&mut self, node: Node) -> Bit {
+        if let Some(&bit) = self.ids.get(&node) {
+            return bit;
+        }
+        let bit = Bit(self.nodes.len() as u32);
+        self.nodes.push(node);
+        self.ids.insert(node, bit);
+        bit
+    }
+
+    fn value(&self, bit: Bit) -> Option<bool> {
+        match self.nodes[bit.0 as usize] {
+            Node::Const(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn constant(&self, value: bool) -> Bit {
+        Bit(value as u32)
+    }
+
+    pub fn var(&mut self, idx: u8) -> Bit {
+        self.add(Node::Var(idx))
+    }
+
+    pub fn not(&mut self, x: Bit) -> Bit {
+        match self.nodes[x.0 as usize] {
+            Node::Const(value) => self.constant(!value),
+            Node::Not(y) => y,
+            _ => self.add(Node::Not(x)),
+        }
+    }
+
+    pub fn and(&mut self, x: Bit, y: Bit) -> Bit {
+        match (self.value(x), self.value(y)) {
+            (Some(false), _) | (_, Some(false)) => self.constant(false),
+            (Some(true), _) => y,
+            (_, Some(true)) => x,
+            _ if x == y => x,
+            _ => self.add(Node::And(x.min(y), x.max(y))),
+        }
+    }
+
+    pub fn or(&mut self, x: Bit, y: Bit) -> Bit {
+        match (self.value(x), self.value(y)) {
+            (Some(true), _) | (_, Some(true)) => self.constant(true),
+            (Some(false), _) => y,
+            (_, Some(false)) => x,
+            _ if x == y => x,
+            _ => self.add(Node::Or(x.min(y), x.max(y))),
+        }
+    }
+
+    pub fn xor(&mut self, x: Bit, y: Bit) -> Bit {
+        match (self.value(x), self.value(y)) {
+            (Some(x), Some(y)) => self.constant(x != y),
+            (Some(false), _) => y,
+            (_, Some(false)) => x,
+            (Some(true), _) => self.not(y),
+            (_, Some(true)) => self.not(x),
+            _ if x == y => self.constant(false),
+            _ => self.add(Node::Xor(x.min(y), x.max(y))),
+        }
+    }
+
+    pub fn ite(&mut self, condition: Bit, then: Bit, otherwise: Bit) -> Bit {
+        match (
+            self.value(condition),
+            self.value(then),
+            self.value(otherwise),
+        ) {
+            (Some(true), _, _) => then,
+            (Some(false), _, _) => otherwise,
+            _ if then == otherwise => then,
+            (_, Some(true), Some(false)) => condition,
+            (_, Some(false), Some(true)) => self.not(condition),
+            _ => self.add(Node::Ite(condition, then, otherwise)),
+        }
+    }
+
+    pub fn word(&self, value: u64) -> Word {
+        std::array::from_fn(|idx| self.constant(value >> idx & 1 == 1))
+    }
+
+    /// The unknown initial value of A.
+    pub fn var_word(&mut self) -> Word {
+        std::array::from_fn(|idx| self.var(idx as u8))
+    }
+
+    pub fn xor_word(&mut self, x: &Word, y: &Word) -> Word {
+        std::array::from_fn(|idx| self.xor(x[idx], y[idx]))
+    }
+
+    /// Lowest three bits of the value, which is the value modulo 8.
+    pub fn low_bits(&self, x: &Word) -> Word {
+        std::array::from_fn(|idx| {
+            if idx < 3 {
+                x[idx]
+            } else {
+                self.constant(false)
+            }
+        })
+    }
+
+    /// Logical shift to the right by an unknown amount. Amounts of 64 and more give zero, like
+    /// [Computer::shr].
+    pub fn shr_word(&mut self, x: &Word, amount: &Word) -> Word {
+        let zero = self.constant(false);
+        let mut result = *x;
+        for (stage, &condition) in amount[..6].iter().enumerate() {
+            let distance = 1 << stage;
+            let shifted: Word = std::array::from_fn(|idx| match result.get(idx + distance) {
+                Some(&bit) => bit,
+                None => zero,
+            });
+            result = std::array::from_fn(|idx| self.ite(condition, shifted[idx], result[idx]));
+        }
+        let overflow = amount[6..]
+            .iter()
+            .fold(zero, |overflow, &bit| self.or(overflow, bit));
+        std::array::from_fn(|idx| self.ite(overflow, zero, result[idx]))
+    }
+
+    /// The value is not zero.
+    pub fn any(&mut self, x: &Word) -> Bit {
+        x.iter()
+            .fold(self.constant(false), |any, &bit| self.or(any, bit))
+    }
+
+    /// Values of all expressions for the bits of `a` which are set in `known`. The values which
+    /// depend on other bits are [None].
+    fn evaluate(&self, known: u64, a: u64) -> Vec<Option<bool>> {
+        let mut values: Vec<Option<bool>> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let value = |bit: Bit| values[bit.0 as usize];
+            let result = match *node {
+                Node::Const(value) => Some(value),
+                Node::Var(idx) => (known >> idx & 1 == 1).then_some(a >> idx & 1 == 1),
+                Node::Not(x) => value(x).map(|x| !x),
+                Node::And(x, y) => match (value(x), value(y)) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                },
+                Node::Or(x, y) => match (value(x), value(y)) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                },
+                Node::Xor(x, y) => value(x).zip(value(y)).map(|(x, y)| x != y),
+                Node::Ite(condition, then, otherwise) => match value(condition) {
+                    Some(true) => value(then),
+                    Some(false) => value(otherwise),
+                    None => value(then).filter(|&then| Some(then) == value(otherwise)),
+                },
+            };
+            values.push(result);
+        }
+        values
+    }
+
+    /// Value of the bits for a known initial value of A, lowest bit first.
+    pub fn eval(&self, bits: &[Bit], a: u64) -> u64 {
+        let values = self.evaluate(u64::MAX, a);
+        bits.iter()
+            .enumerate()
+            .map(|(idx, bit)| (values[bit.0 as usize].unwrap() as u64) << idx)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolicError {
+    /// The program does not end with `jnz 0`.
+    NoFinalJump,
+    /// The instruction at the address is a jump, invalid or uses the reserved combo operand.
+    Unsupported { address: usize },
+    /// The output values of an iteration don't fit the length of the program.
+    OutputCount {
+        per_iteration: usize,
+        program_len: usize,
+    },
+    /// The solver gave up after this many assigned bits.
+    DecisionLimit { decisions: usize },
+}
+
+impl Display for SymbolicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolicError::NoFinalJump => write!(f, "the program does not end with jnz 0"),
+            SymbolicError::Unsupported { address } => write!(
+                f,
+                "the instruction at address {} can not be evaluated symbolically",
+                address
+            ),
+            SymbolicError::OutputCount {
+                per_iteration,
+                program_len,
+            } => write!(
+                f,
+                "{} outputs per iteration don't add up to the {} values of the program",
+                per_iteration, program_len
+            ),
+            SymbolicError::DecisionLimit { decisions } => {
+                write!(f, "no solution within {} assigned bits", decisions)
+            }
+        }
+    }
+}
+
+/// The loop of a program unrolled for a number of iterations.
+pub struct SymbolicLoop {
+    pub exprs: Expressions,
+    /// Output values of all iterations. Only the lowest three bits can be set.
+    pub outputs: Vec<Word>,
+    /// Value of A at the end of every iteration, which decides whether the loop continues.
+    pub a_after: Vec<Word>,
+}
+
+impl SymbolicLoop {
+    /// Unrolls the loop of a program which ends with `jnz 0`. B and C start with the values of
+    /// the computer, and A is unknown.
+    pub fn new(
+        computer: &Computer,
+        memory: &[u8],
+        iterations: usize,
+    ) -> Result<Self, SymbolicError> {
+        if memory.len() < 2 || !memory.ends_with(&[Instruction::Jnz as u8, 0]) {
+            return Err(SymbolicError::NoFinalJump);
+        }
+        let body = (0..memory.len() - 2)
+            .step_by(2)
+            .map(|address| match decode(memory, address) {
+                Ok(decoded)
+                    if decoded.instruction != Instruction::Jnz
+                        && decoded.operand != Operand::Combo(ComboOperand::Reserved) =>
+                {
+                    Ok(decoded)
+                }
+                _ => Err(SymbolicError::Unsupported { address }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut exprs = Expressions::new();
+        let mut a = exprs.var_word();
+        let mut b = exprs.word(computer.b);
+        let mut c = exprs.word(computer.c);
+        let mut outputs = Vec::new();
+        let mut a_after = Vec::new();
+        for _ in 0..iterations {
+            for decoded in &body {
+                let combo = match decoded.operand {
+                    Operand::Combo(ComboOperand::Literal(value)) => exprs.word(value as u64),
+                    Operand::Combo(ComboOperand::A) => a,
+                    Operand::Combo(ComboOperand::B) => b,
+                    Operand::Combo(ComboOperand::C) => c,
+                    Operand::Literal(value) => exprs.word(value as u64),
+                    _ => exprs.word(0),
+                };
+                match decoded.instruction {
+                    Instruction::Adv => a = exprs.shr_word(&a, &combo),
+                    Instruction::Bdv => b = exprs.shr_word(&a, &combo),
+                    Instruction::Cdv => c = exprs.shr_word(&a, &combo),
+                    Instruction::Bst => b = exprs.low_bits(&combo),
+                    Instruction::Bxl => b = exprs.xor_word(&b, &combo),
+                    Instruction::Bxc => b = exprs.xor_word(&b, &c),
+                    Instruction::Out => outputs.push(exprs.low_bits(&combo)),
+                    Instruction::Jnz => unreachable!("the loop body has no jumps"),
+                }
+            }
+            a_after.push(a);
+        }
+        Ok(Self {
+            exprs,
+            outputs,
+            a_after,
+        })
+    }
+
+    /// Constraints for outputting `expected` and halting afterwards: the outputs match, A is not
+    /// zero after all but the last iteration, and zero after the last one.
+    pub fn output_constraints(&mut self, expected: &[u8]) -> Vec<Bit> {
+        let mut constraints = Vec::new();
+        for (output, &value) in self.outputs.iter().zip(expected) {
+            for (idx, &bit) in output[..3].iter().enumerate() {
+                let constraint = match value >> idx & 1 {
+                    1 => bit,
+                    _ => self.exprs.not(bit),
+                };
+                constraints.push(constraint);
+            }
+        }
+        for (iteration, a) in self.a_after.iter().enumerate() {
+            let any = self.exprs.any(a);
+            constraints.push(match iteration + 1 == self.a_after.len() {
+                true => self.exprs.not(any),
+                false => any,
+            });
+        }
+        constraints
+    }
+}
+
+/// Backtracking search for the lowest value of A which satisfies all constraints. The bits are
+/// assigned from the highest to the lowest, trying 0 before 1, so the first solution is the
+/// lowest one. A branch is abandoned as soon as a constraint is false for the assigned bits.
+pub fn solve(
+    exprs: &Expressions,
+    constraints: &[Bit],
+    max_decisions: usize,
+) -> Result<Option<u64>, SymbolicError> {
+    fn search(
+        exprs: &Expressions,
+        constraints: &[Bit],
+        known: u64,
+        a: u64,
+        decisions: &mut usize,
+        max_decisions: usize,
+    ) -> Result<Option<u64>, SymbolicError> {
+        let values = exprs.evaluate(known, a);
+        if constraints
+            .iter()
+            .any(|bit| values[bit.0 as usize] == Some(false))
+        {
+            return Ok(None);
+        }
+        if known == u64::MAX {
+            return Ok(Some(a));
+        }
+        let idx = 63 - known.leading_ones();
+        for value in [0, 1] {
+            *decisions += 1;
+            if *decisions > max_decisions {
+                return Err(SymbolicError::DecisionLimit {
+                    decisions: max_decisions,
+                });
+            }
+            let known = known | 1 << idx;
+            let a = a | value << idx;
+            if let Some(a) = search(exprs, constraints, known, a, decisions, max_decisions)? {
+                return Ok(Some(a));
+            }
+        }
+        Ok(None)
+    }
+    search(exprs, constraints, 0, 0, &mut 0, max_decisions)
+}
+
+/// Finds the lowest value of register A which makes a program ending with `jnz 0` output itself.
+/// Returns [None] if there is no such value.
+pub fn find_quine(
+    computer: &Computer,
+    memory: &[u8],
+    max_decisions: usize,
+) -> Result<Option<u64>, SymbolicError> {
+    let per_iteration = SymbolicLoop::new(computer, memory, 1)?.outputs.len();
+    if per_iteration == 0 || !memory.len().is_multiple_of(per_iteration) {
+        return Err(SymbolicError::OutputCount {
+            per_iteration,
+            program_len: memory.len(),
+        });
+    }
+    let mut symbolic = SymbolicLoop::new(computer, memory, memory.len() / per_iteration)?;
+    let constraints = symbolic.output_constraints(memory);
+    solve(&symbolic.exprs, &constraints, max_decisions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assemble;
+
+    #[test]
+    fn symbolic_outputs_match_execution() {
+        let programs = [
+            vec![2, 4, 1, 5, 7, 5, 1, 6, 4, 1, 5, 5, 0, 3, 3, 0],
+            assemble("adv 1\nout a\nbdv 2\nbxl 3\nout b\njnz 0").unwrap(),
+            assemble("bst a\nbxc\ncdv b\nout c\nadv b\njnz 0").unwrap(),
+        ];
+        for program in &programs {
+            let symbolic = SymbolicLoop::new(&Computer::new(0, 5, 9), program, 3).unwrap();
+            for a in [1, 7, 2024, 117440, 1 << 40, u64::MAX] {
+                let mut computer = Computer::new(a, 5, 9);
+                let execution = computer.execute_with_budget(program, 100);
+                let iterations = symbolic
+                    .a_after
+                    .iter()
+                    .position(|word| symbolic.exprs.eval(word, a) == 0)
+                    .map_or(3, |idx| idx + 1);
+                let outputs: Vec<u8> = symbolic.outputs[..iterations * symbolic.outputs.len() / 3]
+                    .iter()
+                    .map(|word| symbolic.exprs.eval(word, a) as u8)
+                    .collect();
+                assert!(
+                    execution.output.starts_with(&outputs),
+                    "program {:?}, a = {}",
+                    program,
+                    a
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn quines() {
+        let computer = Computer::new(0, 0, 0);
+        let input = [2, 4, 1, 5, 7, 5, 1, 6, 4, 1, 5, 5, 0, 3, 3, 0];
+        assert_eq!(
+            find_quine(&computer, &input, 10_000),
+            Ok(Some(107413700225434))
+        );
+        assert_eq!(
+            find_quine(&computer, &[0, 3, 5, 4, 3, 0], 10_000),
+            Ok(Some(117440))
+        );
+        // The example of part 1 outputs A before it is shifted, so the last output is never 0.
+        assert_eq!(find_quine(&computer, &[0, 1, 5, 4, 3, 0], 10_000), Ok(None));
+    }
+
+    #[test]
+    fn unsupported_programs() {
+        let computer = Computer::new(0, 0, 0);
+        let quine = |source| find_quine(&computer, &assemble(source).unwrap(), 100);
+        assert_eq!(quine("adv 3\nout a"), Err(SymbolicError::NoFinalJump));
+        assert_eq!(
+            quine("out a\nl: jnz l\njnz 0"),
+            Err(SymbolicError::Unsupported { address: 2 })
+        );
+        assert_eq!(
+            quine("out a\nout a\nout a\njnz 0"),
+            Err(SymbolicError::OutputCount {
+                per_iteration: 3,
+                program_len: 8
+            })
+        );
+        assert_eq!(
+            find_quine(
+                &computer,
+                &[2, 4, 1, 5, 7, 5, 1, 6, 4, 1, 5, 5, 0, 3, 3, 0],
+                10
+            ),
+            Err(SymbolicError::DecisionLimit { decisions: 10 })
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashSet;
+
+use aoc_common::{input_lines, Answer, InputMeta, ParseError, Solver};
+use aoc_grid::{Coord2D, Direction};
+use aoc_search::bfs;
+
+/// Memory dimension and number of fallen bytes for part 1 for the puzzle input. Other inputs
+/// specify these with the `dim` and `bytes` metadata parameters.
+pub const DIM_INPUT: usize = 71;
+pub const BYTES_P1_INPUT: usize = 1024;
+
+pub struct Memory {
+    pub x_dim: usize,
+    pub y_dim: usize,
+    pub corrupted: HashSet<Coord2D>,
+}
+
+impl Memory {
+    pub fn new(x_dim: usize, y_dim: usize, corrupted: HashSet<Coord2D>) -> Self {
+        Self {
+            x_dim,
+            y_dim,
+            corrupted,
+        }
+    }
+
+    fn neighbors(&self, coord: &Coord2D) -> impl Iterator<Item = Coord2D> + '_ {
+        let coord = *coord;
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| coord.checked_step(dir))
+            .filter(|next| {
+                next.x < self.x_dim && next.y < self.y_dim && !self.corrupted.contains(next)
+            })
+    }
+
+    /// Shortest path from the top left to the bottom right corner, including both corners.
+    pub fn find_shortest_path(&self) -> Option<Vec<Coord2D>> {
+        let exit = Coord2D::new(self.x_dim - 1, self.y_dim - 1);
+        bfs(
+            Coord2D::new(0, 0),
+            |coord| self.neighbors(coord),
+            |coord| *coord == exit,
+        )
+        .map(|path| path.states)
+    }
+}
+
+pub fn parse_corrupted_bytes(data: &[u8]) -> Result<Vec<Coord2D>, ParseError> {
+    let mut corrupted = Vec::new();
+    for line in input_lines(data)? {
+        let (x, y) = line.split_once(",")?;
+        corrupted.push(Coord2D::new(
+            line.parse(x, "a coordinate")?,
+            line.parse(y, "a coordinate")?,
+        ));
+    }
+    Ok(corrupted)
+}
+
+/// Number of steps of the shortest path after the first `num_bytes` bytes have fallen.
+pub fn steps_after_bytes(corrupted: &[Coord2D], dim: usize, num_bytes: usize) -> Option<usize> {
+    let corrupted_set: HashSet<Coord2D> = corrupted[0..num_bytes].iter().cloned().collect();
+    let memory = Memory::new(dim, dim, corrupted_set);
+    memory.find_shortest_path().map(|path| path.len() - 1)
+}
+
+/// Returns the number of fallen bytes after which the exit is not reachable anymore. The search
+/// starts after the given number of bytes which are known to still allow a path.
+pub fn first_blocking_byte(corrupted: &[Coord2D], dim: usize, num_bytes: usize) -> Option<usize> {
+    // Binary search: once the exit is blocked, more fallen bytes can not open a path again.
+    let mut low = num_bytes + 1;
+    let mut high = corrupted.len() + 1;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if steps_after_bytes(corrupted, dim, mid).is_none() {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    (low <= corrupted.len()).then_some(low)
+}
+
+#[derive(Debug, Clone)]
+pub struct FallingBytes {
+    pub corrupted: Vec<Coord2D>,
+    pub dim: usize,
+    /// Number of fallen bytes for part 1.
+    pub num_bytes: usize,
+}
+
+impl FallingBytes {
+    pub fn new(data: &[u8], meta: &InputMeta) -> Result<Self, ParseError> {
+        let corrupted = parse_corrupted_bytes(data)?;
+        let num_bytes = meta.get_or("bytes", BYTES_P1_INPUT)?;
+        if num_bytes > corrupted.len() {
+            return Err(ParseError::input(format!(
+                "expected at least {} falling bytes, found {}",
+                num_bytes,
+                corrupted.len()
+            )));
+        }
+        Ok(Self {
+            corrupted,
+            dim: meta.get_or("dim", DIM_INPUT)?,
+            num_bytes,
+        })
+    }
+}
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Input = FallingBytes;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        FallingBytes::new(data, &InputMeta::default())
+    }
+
+    fn parse_with_meta(data: &[u8], meta: &InputMeta) -> Result<Self::Input, ParseError> {
+        FallingBytes::new(data, meta)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        match steps_after_bytes(&input.corrupted, input.dim, input.num_bytes) {
+            Some(steps) => steps.into(),
+            None => Answer::Unsolved,
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        match first_blocking_byte(&input.corrupted, input.dim, input.num_bytes) {
+            Some(corruption_idx) => {
+                let byte = input.corrupted[corruption_idx - 1];
+                format!("{},{}", byte.x, byte.y).into()
+            }
+            None => Answer::Unsolved,
+        }
+    }
+}
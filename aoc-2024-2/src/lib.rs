@@ -0,0 +1,115 @@
+use aoc_common::{input_lines, Answer, ParseError, Solver};
+
+const DEBUG: bool = false;
+
+pub fn parse_reports(data: &[u8]) -> Result<Vec<Vec<i32>>, ParseError> {
+    let mut reports = Vec::new();
+    for line in input_lines(data)? {
+        let values = line
+            .text
+            .split_whitespace()
+            .map(|value| line.parse::<i32>(value, "a level"))
+            .collect::<Result<Vec<i32>, ParseError>>()?;
+        if values.len() <= 1 {
+            return Err(line.error(0, "expected a report with at least two levels"));
+        }
+        reports.push(values);
+    }
+    Ok(reports)
+}
+
+/// Returns the number of safe reports for part 1 and part 2.
+pub fn count_safe_reports(reports: &[Vec<i32>]) -> (usize, usize) {
+    let mut safe_lines_part1 = 0;
+    let mut safe_lines_part2 = 0;
+    for values in reports {
+        if part1(values) {
+            safe_lines_part1 += 1;
+            safe_lines_part2 += 1;
+        } else if part2(values) {
+            safe_lines_part2 += 1;
+        }
+    }
+    (safe_lines_part1, safe_lines_part2)
+}
+
+pub fn part1(values: &[i32]) -> bool {
+    let increasing = values[1] > values[0];
+    !unsafe_line_dumb(values, increasing)
+}
+
+pub fn unsafe_line_dumb(values: &[i32], increasing: bool) -> bool {
+    let mut unsafe_line = false;
+    for (idx, val) in values.iter().enumerate() {
+        if idx == 0 {
+            continue;
+        }
+        if increasing {
+            if *val <= values[idx - 1] {
+                unsafe_line = true;
+                break;
+            }
+        } else if *val >= values[idx - 1] {
+            unsafe_line = true;
+            break;
+        }
+        if values[idx - 1].abs_diff(*val) > 3 {
+            unsafe_line = true;
+            break;
+        }
+    }
+    unsafe_line
+}
+
+pub fn unsafe_line_smart(values: &[i32], increasing: bool) -> bool {
+    values
+        .iter()
+        .zip(values.iter().skip(1))
+        .any(|(&prev, &curr)| {
+            (increasing && curr <= prev) || (!increasing && curr >= prev) || prev.abs_diff(curr) > 3
+        })
+}
+
+pub fn part2(values: &[i32]) -> bool {
+    if DEBUG {
+        println!("values not safe, part 2 check for {:?}", values);
+    }
+    let num_values = values.len();
+    for current_skip_idx in 0..num_values {
+        let mut new_list = Vec::new();
+        for (idx, val) in values.iter().enumerate() {
+            if current_skip_idx == idx {
+                continue;
+            }
+            new_list.push(*val);
+        }
+        if new_list.len() <= 1 {
+            panic!("invalid row");
+        }
+        if DEBUG {
+            println!("checking new list {:?}", new_list);
+        }
+        if !unsafe_line_smart(&new_list, new_list[1] > new_list[0]) {
+            return true;
+        }
+    }
+    false
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_reports(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_safe_reports(input).0.into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        count_safe_reports(input).1.into()
+    }
+}
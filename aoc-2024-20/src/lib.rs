@@ -0,0 +1,163 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::{Answer, ParseError, Solver};
+use aoc_grid::{Coord2D, Direction, Grid};
+use aoc_search::{bfs, bfs_distances};
+
+const DEBUG: bool = false;
+
+#[derive(Debug)]
+pub struct Racetrack {
+    pub x_dim: usize,
+    pub y_dim: usize,
+    pub start: Coord2D,
+    pub end: Coord2D,
+    pub walls: HashSet<Coord2D>,
+}
+
+impl Racetrack {
+    pub fn new_from_data(data: &[u8]) -> Result<Self, ParseError> {
+        let grid = Grid::parse(data, "racetrack", b"#.SE")?.without_border();
+        Ok(Self {
+            x_dim: grid.rows(),
+            y_dim: grid.cols(),
+            start: grid.find_required(b'S', "racetrack")?,
+            end: grid.find_required(b'E', "racetrack")?,
+            walls: grid.find_all(b'#').collect(),
+        })
+    }
+
+    fn track_neighbors(&self, coord: &Coord2D) -> impl Iterator<Item = Coord2D> + '_ {
+        let coord = *coord;
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| coord.checked_step(dir))
+            .filter(|next| next.x < self.x_dim && next.y < self.y_dim && !self.walls.contains(next))
+    }
+
+    pub fn try_all_cheat_combinations(&self) -> HashMap<usize, u32> {
+        let from_start = bfs_distances(self.start, |coord| self.track_neighbors(coord));
+        let from_end = bfs_distances(self.end, |coord| self.track_neighbors(coord));
+        let mut picosecond_with_cheats = HashMap::new();
+        for wall in &self.walls {
+            if wall.x > 0 && wall.x < self.x_dim - 1 {
+                let north = Coord2D::new(wall.x - 1, wall.y);
+                let south = Coord2D::new(wall.x + 1, wall.y);
+                if !self.walls.contains(&north) && !self.walls.contains(&south) {
+                    let picoseconds = self.shortest_time_with_cheat(*wall, &from_start, &from_end);
+                    *picosecond_with_cheats.entry(picoseconds).or_insert(0_u32) += 1;
+                }
+            }
+            if wall.y > 0 && wall.y < self.y_dim - 1 {
+                let west = Coord2D::new(wall.x, wall.y - 1);
+                let east = Coord2D::new(wall.x, wall.y + 1);
+                if !self.walls.contains(&east) && !self.walls.contains(&west) {
+                    let picoseconds = self.shortest_time_with_cheat(*wall, &from_start, &from_end);
+                    *picosecond_with_cheats.entry(picoseconds).or_insert(0_u32) += 1;
+                }
+            }
+            if DEBUG {
+                println!("cheating with wall: {:?}", wall);
+            }
+        }
+        picosecond_with_cheats
+    }
+
+    /// Maps the picoseconds saved by a cheat to the number of cheats saving that much time.
+    pub fn saved_times(&self) -> HashMap<usize, u32> {
+        let picoseconds_no_cheats = self.find_shortest_path().map_or(0, |path| path.len() - 1);
+        let picosecond_with_cheats = self.try_all_cheat_combinations();
+        picosecond_with_cheats
+            .iter()
+            .filter(|(picoseconds, _)| **picoseconds < picoseconds_no_cheats)
+            .map(|(picoseconds, times)| (picoseconds_no_cheats - *picoseconds, *times))
+            .collect()
+    }
+
+    /// Shortest time if the given wall is removed, based on the distances of all track tiles
+    /// from the start and from the end. A path either avoids the removed wall or enters it from
+    /// one neighbouring track tile and leaves it to another one.
+    pub fn shortest_time_with_cheat(
+        &self,
+        cheat: Coord2D,
+        from_start: &HashMap<Coord2D, usize>,
+        from_end: &HashMap<Coord2D, usize>,
+    ) -> usize {
+        let neighbors: Vec<Coord2D> = self.track_neighbors(&cheat).collect();
+        let mut shortest = from_start.get(&self.end).copied();
+        for entry in &neighbors {
+            for exit in neighbors.iter().filter(|exit| *exit != entry) {
+                if let (Some(to_entry), Some(from_exit)) =
+                    (from_start.get(entry), from_end.get(exit))
+                {
+                    let picoseconds = to_entry + 2 + from_exit;
+                    shortest = Some(shortest.map_or(picoseconds, |v| v.min(picoseconds)));
+                }
+            }
+        }
+        shortest.unwrap_or(0)
+    }
+
+    /// Shortest path from the start to the end without cheating, including both tiles.
+    pub fn find_shortest_path(&self) -> Option<Vec<Coord2D>> {
+        bfs(
+            self.start,
+            |coord| self.track_neighbors(coord),
+            |coord| *coord == self.end,
+        )
+        .map(|path| path.states)
+    }
+}
+
+pub fn cheats_saving_at_least(saved_times: &HashMap<usize, u32>, picoseconds: usize) -> u32 {
+    let mut cheats = 0;
+    for (saved_time, num) in saved_times {
+        if *saved_time >= picoseconds {
+            cheats += num;
+        }
+    }
+    cheats
+}
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Input = Racetrack;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        Racetrack::new_from_data(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        cheats_saving_at_least(&input.saved_times(), 100).into()
+    }
+
+    fn part2(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_example() {
+        let input_file = std::fs::read("example.txt").unwrap();
+        let racetrack = Racetrack::new_from_data(&input_file).unwrap();
+        let saved_times = racetrack.saved_times();
+        assert_eq!(racetrack.x_dim, 13);
+        assert_eq!(racetrack.y_dim, 13);
+        assert_eq!(racetrack.start, Coord2D::new(2, 0));
+        assert_eq!(racetrack.end, Coord2D::new(6, 4));
+        assert_eq!(*saved_times.get(&64).unwrap(), 1);
+        assert_eq!(*saved_times.get(&40).unwrap(), 1);
+        assert_eq!(*saved_times.get(&38).unwrap(), 1);
+        assert_eq!(*saved_times.get(&20).unwrap(), 1);
+        assert_eq!(*saved_times.get(&12).unwrap(), 3);
+        assert_eq!(*saved_times.get(&10).unwrap(), 2);
+        assert_eq!(*saved_times.get(&8).unwrap(), 4);
+        assert_eq!(*saved_times.get(&6).unwrap(), 2);
+        assert_eq!(*saved_times.get(&4).unwrap(), 14);
+        assert_eq!(*saved_times.get(&2).unwrap(), 14);
+    }
+}
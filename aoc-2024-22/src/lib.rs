@@ -0,0 +1,83 @@
+use aoc_common::{input_lines, Answer, ParseError, Solver};
+
+pub fn parse_secrets(data: &[u8]) -> Result<Vec<u64>, ParseError> {
+    input_lines(data)?
+        .map(|line| line.parse::<u64>(line.text, "a secret number"))
+        .collect()
+}
+
+/// Sum of all secret numbers after they have evolved the given number of times.
+pub fn sum_of_evolved(secrets: &[u64], times: usize) -> u64 {
+    let mut sum = 0;
+    for &number in secrets {
+        let mut evolved = number;
+        for _ in 0..times {
+            evolved = evolve_number(evolved);
+        }
+        sum += evolved;
+    }
+    sum
+}
+
+pub fn evolve_number(mut number: u64) -> u64 {
+    let multiplied = number * 64;
+    number ^= multiplied;
+    number %= 16777216;
+    let divided = number / 32;
+    number ^= divided;
+    number %= 16777216;
+    let multiplied = number * 2048;
+    number ^= multiplied;
+    number % 16777216
+}
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Input = Vec<u64>;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_secrets(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        sum_of_evolved(input, 2000).into()
+    }
+
+    fn part2(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evolve() {
+        let number = 123;
+        let mut evolved = evolve_number(number);
+        assert_eq!(evolved, 15887950);
+        evolved = evolve_number(evolved);
+        assert_eq!(evolved, 16495136);
+    }
+
+    #[test]
+    fn test_example() {
+        let init_nums = [1, 10, 100, 2024];
+        let mut evolved;
+        for (idx, num) in init_nums.iter().enumerate() {
+            evolved = *num;
+            for _ in 0..2000 {
+                evolved = evolve_number(evolved);
+            }
+            match idx {
+                0 => assert_eq!(evolved, 8685429),
+                1 => assert_eq!(evolved, 4700978),
+                2 => assert_eq!(evolved, 15273692),
+                3 => assert_eq!(evolved, 8667524),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::{input_lines, Answer, ParseError, Solver};
+
+pub type ComputerMap = HashMap<String, HashSet<String>>;
+pub fn read_computer_map(data: &[u8]) -> Result<ComputerMap, ParseError> {
+    let mut computer_map = HashMap::new();
+    for line in input_lines(data)? {
+        let (first, second) = line.split_once("-")?;
+        for name in [first, second] {
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase()) {
+                return Err(line.expected(name, "a computer name"));
+            }
+        }
+        let computer_pair = [first, second];
+        computer_map
+            .entry(computer_pair[0].to_string())
+            .or_insert_with(|| {
+                let mut hashset = HashSet::new();
+                hashset.insert(computer_pair[1].to_string());
+                hashset
+            })
+            .insert(computer_pair[1].to_string());
+        computer_map
+            .entry(computer_pair[1].to_string())
+            .or_insert_with(|| {
+                let mut hashset = HashSet::new();
+                hashset.insert(computer_pair[0].to_string());
+                hashset
+            })
+            .insert(computer_pair[0].to_string());
+    }
+    Ok(computer_map)
+}
+
+pub fn find_triple_sets(computer_map: &ComputerMap) -> HashSet<Vec<&String>> {
+    let mut triple_sets = HashSet::new();
+    for (pc, connected_list) in computer_map {
+        for (i, connected_pc) in connected_list.iter().enumerate() {
+            for other_connected_pc in connected_list.iter().skip(i + 1) {
+                if computer_map[connected_pc].contains(other_connected_pc)
+                    && computer_map[connected_pc].contains(pc)
+                {
+                    let mut triple_set = vec![pc, connected_pc, other_connected_pc];
+                    triple_set.sort();
+                    // This is the condition for a full set
+                    triple_sets.insert(triple_set);
+                }
+            }
+        }
+    }
+    triple_sets
+}
+/// Number of interconnected sets of three computers where at least one computer name starts
+/// with a `t`.
+pub fn count_sets_with_t(triple_sets: &HashSet<Vec<&String>>) -> usize {
+    triple_sets
+        .iter()
+        .filter(|set| set.iter().any(|s| s.starts_with("t")))
+        .count()
+}
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    type Input = ComputerMap;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        read_computer_map(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_sets_with_t(&find_triple_sets(input)).into()
+    }
+
+    fn part2(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input_file = std::fs::read("example.txt").unwrap();
+        let computer_map = read_computer_map(&input_file).unwrap();
+        let triple_sets = find_triple_sets(&computer_map);
+        assert_eq!(triple_sets.len(), 12);
+        assert_eq!(count_sets_with_t(&triple_sets), 7);
+    }
+}
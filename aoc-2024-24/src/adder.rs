@@ -0,0 +1,370 @@
+//! Structural check of a ripple-carry adder. Every bit `i > 0` of the adder is built from the
+//! same five gates:
+//!
+//! ```text
+//! x_i XOR y_i -> sum_i      sum_i XOR carry_i -> z_i
+//! x_i AND y_i -> gen_i      sum_i AND carry_i -> prop_i      gen_i OR prop_i -> carry_i+1
+//! ```
+//!
+//! Bit 0 is a half adder without an incoming carry, and the carry out of the highest bit is the
+//! last `z` wire. Swapped output wires show up as gates which break this pattern, so the swaps
+//! are found by pairing the outputs of these gates until the pattern holds.
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{
+    bit_index,
+    netlist::{Netlist, NetlistError},
+    Gate, Operator,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Violation {
+    /// A `z` wire other than the last one is not the output of an XOR gate.
+    SumNotXor { wire: String },
+    /// The last `z` wire is not the output of an OR gate.
+    CarryOutNotOr { wire: String },
+    /// An XOR gate neither adds the input bits nor writes a `z` wire.
+    XorInTheMiddle { wire: String },
+    /// The sum of two input bits is not added to the carry by an XOR gate.
+    SumNotToXor { wire: String },
+    /// The output of an AND gate is not combined into a carry by an OR gate.
+    AndNotToOr { wire: String },
+    /// An input of an OR gate is not the output of an AND gate.
+    OrInputNotAnd { wire: String },
+}
+
+impl Violation {
+    /// The wire which is written by the gate breaking the pattern.
+    pub fn wire(&self) -> &str {
+        match self {
+            Violation::SumNotXor { wire }
+            | Violation::CarryOutNotOr { wire }
+            | Violation::XorInTheMiddle { wire }
+            | Violation::SumNotToXor { wire }
+            | Violation::AndNotToOr { wire }
+            | Violation::OrInputNotAnd { wire } => wire,
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::SumNotXor { wire } => write!(f, "{} is not written by an XOR gate", wire),
+            Violation::CarryOutNotOr { wire } => {
+                write!(
+                    f,
+                    "{} is the carry out, but not written by an OR gate",
+                    wire
+                )
+            }
+            Violation::XorInTheMiddle { wire } => write!(
+                f,
+                "{} is written by an XOR gate which neither adds x and y nor writes z",
+                wire
+            ),
+            Violation::SumNotToXor { wire } => {
+                write!(
+                    f,
+                    "{} is the sum of x and y, but no XOR gate reads it",
+                    wire
+                )
+            }
+            Violation::AndNotToOr { wire } => {
+                write!(
+                    f,
+                    "{} is written by an AND gate, but no OR gate reads it",
+                    wire
+                )
+            }
+            Violation::OrInputNotAnd { wire } => {
+                write!(
+                    f,
+                    "{} is read by an OR gate, but not written by an AND gate",
+                    wire
+                )
+            }
+        }
+    }
+}
+
+fn is_input(wire: &str) -> bool {
+    bit_index(wire, 'x').is_some() || bit_index(wire, 'y').is_some()
+}
+
+fn is_output(wire: &str) -> bool {
+    bit_index(wire, 'z').is_some()
+}
+
+fn is_first_bit(gate: &Gate) -> bool {
+    gate.inputs
+        .iter()
+        .any(|wire| bit_index(wire, 'x') == Some(0))
+}
+
+/// Gates which break the pattern of a ripple-carry adder, sorted by the kind of violation.
+pub fn check(gates: &[Gate]) -> Vec<Violation> {
+    let last_z = gates
+        .iter()
+        .filter_map(|gate| Some((bit_index(&gate.output, 'z')?, &gate.output)))
+        .max()
+        .map(|(_, wire)| wire);
+    let reads = |operator: Operator, wire: &str| {
+        gates
+            .iter()
+            .any(|gate| gate.operator == operator && gate.inputs.iter().any(|input| input == wire))
+    };
+    let mut violations = HashSet::new();
+    for gate in gates {
+        let wire = gate.output.clone();
+        let adds_inputs = gate.inputs.iter().all(|wire| is_input(wire));
+        if Some(&gate.output) == last_z {
+            if gate.operator != Operator::Or {
+                violations.insert(Violation::CarryOutNotOr { wire: wire.clone() });
+            }
+        } else if is_output(&gate.output) && gate.operator != Operator::Xor {
+            violations.insert(Violation::SumNotXor { wire: wire.clone() });
+        }
+        match gate.operator {
+            Operator::Xor if !adds_inputs && !is_output(&gate.output) => {
+                violations.insert(Violation::XorInTheMiddle { wire });
+            }
+            Operator::Xor if adds_inputs && !is_first_bit(gate) && !reads(Operator::Xor, &wire) => {
+                violations.insert(Violation::SumNotToXor { wire });
+            }
+            Operator::And if !is_first_bit(gate) && !reads(Operator::Or, &wire) => {
+                violations.insert(Violation::AndNotToOr { wire });
+            }
+            Operator::Or => {
+                for input in &gate.inputs {
+                    let written_by_and = gates
+                        .iter()
+                        .any(|other| &other.output == input && other.operator == Operator::And);
+                    if !written_by_and {
+                        violations.insert(Violation::OrInputNotAnd {
+                            wire: input.clone(),
+                        });
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    let mut violations = Vec::from_iter(violations);
+    violations.sort();
+    violations
+}
+
+/// Swaps the output wires of the gates which write `left` and `right`.
+pub fn swap_outputs(gates: &mut [Gate], left: &str, right: &str) {
+    for gate in gates {
+        if gate.output == left {
+            gate.output = right.to_string();
+        } else if gate.output == right {
+            gate.output = left.to_string();
+        }
+    }
+}
+
+/// Pairs the wires in `wires` until the gates follow the pattern.
+fn pair_up(gates: &mut [Gate], wires: &mut Vec<String>, swaps: &mut Vec<(String, String)>) -> bool {
+    let Some(first) = wires.pop() else {
+        return check(gates).is_empty();
+    };
+    for idx in 0..wires.len() {
+        let other = wires.remove(idx);
+        swap_outputs(gates, &first, &other);
+        swaps.push((first.clone(), other.clone()));
+        if pair_up(gates, wires, swaps) {
+            return true;
+        }
+        swaps.pop();
+        swap_outputs(gates, &first, &other);
+        wires.insert(idx, other);
+    }
+    wires.push(first);
+    false
+}
+
+/// Swaps of output wires which turn the circuit into a ripple-carry adder. Only the wires written
+/// by gates which break the pattern are paired, so this returns [None] if a swapped wire does not
+/// show up as a violation, or if the wires can not be paired.
+pub fn find_swaps(gates: &[Gate]) -> Option<Vec<(String, String)>> {
+    let mut wires: Vec<String> = check(gates)
+        .iter()
+        .map(|violation| violation.wire().to_string())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    // There are 945 ways to pair 10 wires, and each way checks the whole circuit.
+    if !wires.len().is_multiple_of(2) || wires.len() > 10 {
+        return None;
+    }
+    wires.sort();
+    let mut swaps = Vec::new();
+    pair_up(&mut gates.to_vec(), &mut wires, &mut swaps).then_some(swaps)
+}
+
+/// The swapped wires in sorted order, separated by commas.
+pub fn format_swaps(swaps: &[(String, String)]) -> String {
+    let mut wires: Vec<&str> = swaps
+        .iter()
+        .flat_map(|(left, right)| [left.as_str(), right.as_str()])
+        .collect();
+    wires.sort();
+    wires.join(",")
+}
+
+/// Test pairs of numbers with `width` bits: every combination of the bits at each position and
+/// the position below it, a carry which ripples up to each position, and `random` pseudo-random
+/// pairs.
+fn test_pairs(width: u32, random: usize) -> impl Iterator<Item = (u64, u64)> {
+    let mask = u64::MAX >> (64 - width.clamp(1, 64));
+    let around_bits = (0..width).flat_map(move |idx| {
+        let below = idx.saturating_sub(1);
+        (0..16u64).map(move |bits| {
+            let x = (bits & 1) << idx | (bits >> 1 & 1) << below;
+            let y = (bits >> 2 & 1) << idx | (bits >> 3 & 1) << below;
+            (x, y)
+        })
+    });
+    let carries = (0..width).map(move |idx| ((1 << idx) - 1, 1));
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state & mask
+    };
+    let random = (0..random).map(move |_| (next(), next()));
+    around_bits.chain(carries).chain(random)
+}
+
+/// Adds test pairs with [Netlist::evaluate_batch] and returns the `z` bits which differ from the
+/// sum for at least one pair. The lowest of these bits is the first stage of the adder which is
+/// wired wrong.
+pub fn faulty_bits(netlist: &Netlist, random_pairs: usize) -> Result<u64, NetlistError> {
+    let mut faulty = 0;
+    let mut batch = Vec::with_capacity(64);
+    let mut check = |batch: &[(u64, u64)]| -> Result<(), NetlistError> {
+        for (&(x, y), z) in batch.iter().zip(netlist.evaluate_batch(batch)?) {
+            faulty |= z ^ (x + y);
+        }
+        Ok(())
+    };
+    for pair in test_pairs(netlist.width('x'), random_pairs) {
+        batch.push(pair);
+        if batch.len() == 64 {
+            check(&batch)?;
+            batch.clear();
+        }
+    }
+    check(&batch)?;
+    Ok(faulty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{netlist::Netlist, Circuit, Monitoring};
+
+    /// Input of a ripple-carry adder for numbers with `bits` bits. The wires are named by `name`
+    /// from their kind and bit: `x`, `y` and `z`, and `s`, `c`, `g` and `p` for the sum, carry,
+    /// generate and propagate wires inside the adder.
+    fn adder_named(bits: usize, name: impl Fn(char, usize) -> String) -> String {
+        let mut input = String::new();
+        for prefix in ['x', 'y'] {
+            for bit in 0..bits {
+                input += &format!("{}: 1\n", name(prefix, bit));
+            }
+        }
+        let (x, y) = (name('x', 0), name('y', 0));
+        input += &format!(
+            "\n{x} XOR {y} -> {}\n{x} AND {y} -> {}\n",
+            name('z', 0),
+            name('c', 1)
+        );
+        for bit in 1..bits {
+            let carry_out = match bit + 1 == bits {
+                true => name('z', bits),
+                false => name('c', bit + 1),
+            };
+            let [x, y, z, s, c, g, p] =
+                ['x', 'y', 'z', 's', 'c', 'g', 'p'].map(|kind| name(kind, bit));
+            input += &format!(
+                "{x} XOR {y} -> {s}\n{s} XOR {c} -> {z}\n{x} AND {y} -> {g}\n\
+                {s} AND {c} -> {p}\n{g} OR {p} -> {carry_out}\n"
+            );
+        }
+        input
+    }
+
+    fn adder(bits: usize) -> String {
+        adder_named(bits, |kind, bit| format!("{}{:02}", kind, bit))
+    }
+
+    fn adds(circuit: &Circuit) -> bool {
+        let netlist = Netlist::new(circuit).unwrap();
+        (0..64).all(|x| (0..64).all(|y| netlist.evaluate(x, y) == Ok(x + y)))
+    }
+
+    #[test]
+    fn intact_adder() {
+        let mut monitoring = Monitoring::new(adder(6).as_bytes()).unwrap();
+        assert_eq!(check(&monitoring.circuit.gates), vec![]);
+        assert_eq!(find_swaps(&monitoring.circuit.gates), Some(vec![]));
+        assert_eq!(monitoring.simulate(), Ok(63 + 63));
+        assert!(adds(&monitoring.circuit));
+        let netlist = Netlist::new(&monitoring.circuit).unwrap();
+        assert_eq!(faulty_bits(&netlist, 1000), Ok(0));
+    }
+
+    #[test]
+    fn wire_names() {
+        // Bits without zero padding, and wires inside the adder which start with x, y or z.
+        let input = adder_named(12, |kind, bit| match kind {
+            's' => format!("xs{}", bit),
+            'c' => format!("zc{}", bit),
+            'g' => format!("yg{}", bit),
+            'p' => format!("zp{}", bit),
+            _ => format!("{}{}", kind, bit),
+        });
+        let monitoring = Monitoring::new(input.as_bytes()).unwrap();
+        assert_eq!(check(&monitoring.circuit.gates), vec![]);
+        assert_eq!(find_swaps(&monitoring.circuit.gates), Some(vec![]));
+        assert!(adds(&monitoring.circuit));
+    }
+
+    #[test]
+    fn swapped_wires() {
+        let input = adder(6)
+            .replace("c03 -> z03", "c03 -> p03")
+            .replace("AND c03 -> p03", "AND c03 -> z03")
+            .replace("y04 -> s04", "y04 -> TMP")
+            .replace("y04 -> g04", "y04 -> s04")
+            .replace("y04 -> TMP", "y04 -> g04");
+        let mut monitoring = Monitoring::new(input.as_bytes()).unwrap();
+        assert!(!adds(&monitoring.circuit));
+        let violations = check(&monitoring.circuit.gates);
+        assert!(violations.contains(&Violation::SumNotXor {
+            wire: "z03".to_string()
+        }));
+        assert_eq!(
+            violations[0].to_string(),
+            "z03 is not written by an XOR gate"
+        );
+        let netlist = Netlist::new(&monitoring.circuit).unwrap();
+        assert_eq!(faulty_bits(&netlist, 1000).unwrap().trailing_zeros(), 3);
+        let swaps = find_swaps(&monitoring.circuit.gates).unwrap();
+        assert_eq!(swaps.len(), 2);
+        assert_eq!(format_swaps(&swaps), "g04,p03,s04,z03");
+        for (left, right) in &swaps {
+            swap_outputs(&mut monitoring.circuit.gates, left, right);
+        }
+        assert!(adds(&monitoring.circuit));
+        let repaired = Monitoring::new(monitoring.to_string().as_bytes()).unwrap();
+        assert_eq!(check(&repaired.circuit.gates), vec![]);
+        let netlist = Netlist::new(&monitoring.circuit).unwrap();
+        assert_eq!(faulty_bits(&netlist, 1000), Ok(0));
+    }
+}
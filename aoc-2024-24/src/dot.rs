@@ -0,0 +1,102 @@
+//! Graphviz export of the gate circuit. Wires are nodes named after the wire, and every gate is a
+//! node shaped after its operator with edges from its input wires and to its output wire. The
+//! `x`, `y` and `z` wires are each kept on one rank in bit order, so the adder reads from the
+//! inputs on the left to the outputs on the right.
+use std::fmt::Display;
+
+use crate::{Monitoring, Operator};
+
+pub struct DotGraph<'a> {
+    monitoring: &'a Monitoring,
+    /// Label the wires with their resolved values.
+    show_values: bool,
+}
+
+impl<'a> DotGraph<'a> {
+    pub fn new(monitoring: &'a Monitoring, show_values: bool) -> Self {
+        Self {
+            monitoring,
+            show_values,
+        }
+    }
+}
+
+fn shape(operator: Operator) -> &'static str {
+    match operator {
+        Operator::And => "box",
+        Operator::Or => "ellipse",
+        Operator::Xor => "diamond",
+        Operator::Not => "triangle",
+        Operator::Nand => "house",
+        Operator::Nor => "invhouse",
+        Operator::Xnor => "hexagon",
+    }
+}
+
+impl Display for DotGraph<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "digraph circuit {{")?;
+        writeln!(f, "    rankdir=LR;")?;
+        writeln!(f, "    node [shape=plaintext];")?;
+        let mut wires: Vec<_> = self.monitoring.wires.iter().collect();
+        wires.sort();
+        for prefix in ['x', 'y', 'z'] {
+            let ranked: Vec<_> = wires
+                .iter()
+                .filter(|(wire, _)| wire.starts_with(prefix))
+                .map(|(wire, _)| format!("{:?};", wire))
+                .collect();
+            if !ranked.is_empty() {
+                writeln!(f, "    {{ rank=same; {} }}", ranked.join(" "))?;
+            }
+        }
+        for (wire, value) in &wires {
+            match value {
+                Some(value) if self.show_values => {
+                    writeln!(f, "    {:?} [label=\"{} = {}\"];", wire, wire, value)?
+                }
+                _ => writeln!(f, "    {:?};", wire)?,
+            }
+        }
+        for (idx, gate) in self.monitoring.circuit.gates.iter().enumerate() {
+            let node = format!("gate{}", idx);
+            writeln!(
+                f,
+                "    {} [label={:?}, shape={}];",
+                node,
+                gate.operator.name(),
+                shape(gate.operator)
+            )?;
+            for input in gate.input_wires() {
+                writeln!(f, "    {:?} -> {};", input, node)?;
+            }
+            writeln!(f, "    {} -> {:?};", node, gate.output)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_graph() {
+        let input_file = std::fs::read("example0.txt").unwrap();
+        let mut monitoring = Monitoring::new(&input_file).unwrap();
+        let graph = DotGraph::new(&monitoring, false).to_string();
+        assert!(graph.contains("    { rank=same; \"x00\"; \"x01\"; \"x02\"; }\n"));
+        assert!(graph.contains("    { rank=same; \"z00\"; \"z01\"; \"z02\"; }\n"));
+        assert!(graph.contains(
+            "    gate1 [label=\"XOR\", shape=diamond];\n    \"x01\" -> gate1;\n    \
+            \"y01\" -> gate1;\n    gate1 -> \"z01\";\n"
+        ));
+        assert!(graph.contains("    \"y01\";\n"));
+
+        monitoring.simulate().unwrap();
+        let graph = DotGraph::new(&monitoring, true).to_string();
+        assert!(graph.contains("    \"y01\" [label=\"y01 = 1\"];\n"));
+        assert!(graph.contains("    \"z02\" [label=\"z02 = 1\"];\n"));
+        assert_eq!(graph.matches(" -> ").count(), 9);
+    }
+}
@@ -0,0 +1,459 @@
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    ops::{BitAnd, BitOr, BitXor, Not},
+};
+
+use aoc_common::{input_lines, Answer, Line, ParseError, Solver};
+
+pub mod adder;
+pub mod dot;
+pub mod netlist;
+
+use netlist::{Netlist, NetlistError};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Operator {
+    And,
+    Or,
+    Xor,
+    Not,
+    Nand,
+    Nor,
+    Xnor,
+}
+
+impl Operator {
+    pub const ALL: [Operator; 7] = [
+        Operator::And,
+        Operator::Or,
+        Operator::Xor,
+        Operator::Not,
+        Operator::Nand,
+        Operator::Nor,
+        Operator::Xnor,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Operator::And => "AND",
+            Operator::Or => "OR",
+            Operator::Xor => "XOR",
+            Operator::Not => "NOT",
+            Operator::Nand => "NAND",
+            Operator::Nor => "NOR",
+            Operator::Xnor => "XNOR",
+        }
+    }
+
+    /// Number of input wires.
+    pub fn arity(self) -> usize {
+        match self {
+            Operator::Not => 1,
+            _ => 2,
+        }
+    }
+}
+
+/// Names of the wires which always have the value 0 and 1. They can be used as gate inputs, like
+/// in `x00 XOR 1 -> z00`.
+pub const CONSTANT_WIRES: [&str; 2] = ["0", "1"];
+
+/// Gate of the circuit as it is written in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gate {
+    /// Unary gates have the same input wire twice.
+    pub inputs: [String; 2],
+    pub operator: Operator,
+    pub output: String,
+}
+
+impl Gate {
+    /// The distinct input wires, which is one wire for a unary gate.
+    pub fn input_wires(&self) -> &[String] {
+        &self.inputs[..self.operator.arity()]
+    }
+}
+
+impl Display for Gate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.input_wires() {
+            [input] => write!(f, "{} {} -> {}", self.operator.name(), input, self.output),
+            inputs => write!(
+                f,
+                "{} {} {} -> {}",
+                inputs[0],
+                self.operator.name(),
+                inputs[1],
+                self.output
+            ),
+        }
+    }
+}
+
+/// Gates of the device without any wire values, so it can be evaluated for any input numbers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Circuit {
+    /// All gates in input order.
+    pub gates: Vec<Gate>,
+}
+
+impl Circuit {
+    /// Sets the `x` and `y` wires to the bits of the numbers and returns the number on the `z`
+    /// wires, or [None] if the circuit has a cycle or an undriven wire. Bits without a wire are
+    /// ignored. The circuit is compiled on every call, so a [Netlist] should be used to evaluate
+    /// it many times.
+    pub fn evaluate(&self, x: u64, y: u64) -> Option<u64> {
+        Netlist::new(self)
+            .and_then(|netlist| netlist.evaluate(x, y))
+            .ok()
+    }
+}
+
+/// The gates in the format of the puzzle input, one per line.
+impl Display for Circuit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for gate in &self.gates {
+            writeln!(f, "{}", gate)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monitoring {
+    /// Initial values of the input wires, and the values of all wires after [Monitoring::simulate].
+    pub wires: HashMap<String, Option<u8>>,
+    pub circuit: Circuit,
+    pub z_vars: HashMap<String, usize>,
+}
+
+/// Bit which the wire carries of the number on the wires starting with `prefix`, like 5 for `z05`.
+/// Only the prefix followed by decimal digits names a bit, so wires like `zbk` carry none, and
+/// neither do wires past the 64 bits of a number.
+pub fn bit_index(wire: &str, prefix: char) -> Option<u32> {
+    let digits = wire.strip_prefix(prefix)?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|&idx| idx < u64::BITS)
+}
+
+/// Rejects `x`, `y` and `z` wires whose bit does not fit into a 64-bit number.
+fn check_bit_index(line: &Line, wire: &str) -> Result<(), ParseError> {
+    let Some(digits) = wire.strip_prefix(['x', 'y', 'z']) else {
+        return Ok(());
+    };
+    let is_bit = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit());
+    if is_bit && bit_index(wire, wire.chars().next().unwrap_or_default()).is_none() {
+        return Err(line.error(
+            line.column_of(wire),
+            format!("wire {:?} is past bit 63 of a 64-bit number", wire),
+        ));
+    }
+    Ok(())
+}
+
+impl Monitoring {
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        let mut wires = HashMap::new();
+        let mut gates = Vec::new();
+        let mut z_vars = HashMap::new();
+        let re = Regex::new(r"^(\w+) (\w+) (\w+) -> (\w+)$").unwrap();
+        let re_unary = Regex::new(r"^(\w+) (\w+) -> (\w+)$").unwrap();
+        for line in input_lines(data)? {
+            if line.text.is_empty() {
+                continue;
+            }
+            if let Some((key, val)) = line.text.split_once(':') {
+                let key = key.trim();
+                if key.is_empty() || !key.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(line.expected(key, "a wire name"));
+                }
+                check_bit_index(&line, key)?;
+                let value = match line.parse::<u8>(val, "a wire value of 0 or 1")? {
+                    value @ (0 | 1) => value,
+                    _ => return Err(line.expected(val.trim(), "a wire value of 0 or 1")),
+                };
+                wires.insert(key.to_string(), Some(value));
+            } else if let Some(captures) = re
+                .captures(line.text)
+                .or_else(|| re_unary.captures(line.text))
+            {
+                // A unary gate like `NOT a -> b` has no first input wire.
+                let unary = captures.len() == 4;
+                let operator_str = captures.get(if unary { 1 } else { 2 }).unwrap().as_str();
+                let op = Operator::ALL
+                    .into_iter()
+                    .find(|op| op.name().eq_ignore_ascii_case(operator_str))
+                    .filter(|op| op.arity() == if unary { 1 } else { 2 })
+                    .ok_or_else(|| match unary {
+                        true => line.expected(operator_str, "NOT"),
+                        false => line.expected(operator_str, "AND, OR, XOR, NAND, NOR or XNOR"),
+                    })?;
+                let (wire0, wire1, dest_wire) = match unary {
+                    true => (&captures[2], &captures[2], &captures[3]),
+                    false => (&captures[1], &captures[3], &captures[4]),
+                };
+                if CONSTANT_WIRES.contains(&dest_wire) {
+                    return Err(line.error(
+                        line.column_of(dest_wire),
+                        format!(
+                            "constant wire {:?} can not be the output of a gate",
+                            dest_wire
+                        ),
+                    ));
+                }
+                if let Some(Some(_)) = wires.get(dest_wire) {
+                    return Err(line.error(
+                        line.column_of(dest_wire),
+                        format!("wire {:?} already has a value", dest_wire),
+                    ));
+                }
+                for wire in [wire0, wire1, dest_wire] {
+                    check_bit_index(&line, wire)?;
+                    if let Some(idx) = bit_index(wire, 'z') {
+                        z_vars.insert(wire.to_string(), idx as usize);
+                    }
+                    let constant = CONSTANT_WIRES.iter().position(|&constant| constant == wire);
+                    wires
+                        .entry(wire.to_string())
+                        .or_insert(constant.map(|value| value as u8));
+                }
+                gates.push(Gate {
+                    inputs: [wire0.to_string(), wire1.to_string()],
+                    operator: op,
+                    output: dest_wire.to_string(),
+                });
+            } else {
+                return Err(line.error(1, "expected an initial wire value or a gate"));
+            }
+        }
+        Ok(Self {
+            wires,
+            circuit: Circuit { gates },
+            z_vars,
+        })
+    }
+
+    /// Applies the operator to every bit of the values, so a `u64` holds 64 independent wire
+    /// values.
+    fn resolve_wire<T>(wire_val_left: T, wire_val_right: T, operator: Operator) -> T
+    where
+        T: BitAnd<Output = T> + BitOr<Output = T> + BitXor<Output = T> + Not<Output = T>,
+    {
+        match operator {
+            Operator::And => wire_val_left & wire_val_right,
+            Operator::Or => wire_val_left | wire_val_right,
+            Operator::Xor => wire_val_left ^ wire_val_right,
+            Operator::Not => !wire_val_left,
+            Operator::Nand => !(wire_val_left & wire_val_right),
+            Operator::Nor => !(wire_val_left | wire_val_right),
+            Operator::Xnor => !(wire_val_left ^ wire_val_right),
+        }
+    }
+
+    /// Evaluates the circuit for the initial wire values, stores the values of all wires and
+    /// returns the number on the z wires.
+    pub fn simulate(&mut self) -> Result<u64, NetlistError> {
+        let netlist = Netlist::new(&self.circuit)?;
+        let values = netlist.evaluate_wires(|wire| self.wires.get(wire).copied().flatten())?;
+        for (id, &value) in values.iter().enumerate() {
+            self.wires.insert(netlist.name(id).to_string(), Some(value));
+        }
+        Ok(netlist.number(&values, 'z'))
+    }
+
+    /// The number on the wires starting with `prefix`, counting wires without a value as 0.
+    pub fn number(&self, prefix: char) -> u64 {
+        let mut number = 0;
+        for (wire, value) in &self.wires {
+            if let (Some(idx), Some(1)) = (bit_index(wire, prefix), value) {
+                number |= 1 << idx;
+            }
+        }
+        number
+    }
+
+    /// The number on the z wires, or [None] if one of them has no value yet.
+    pub fn result(&self) -> Option<u64> {
+        let mut number = 0;
+        for (z_var, idx) in &self.z_vars {
+            number |= (self.wires.get(z_var).copied().flatten()? as u64) << idx;
+        }
+        Some(number)
+    }
+}
+
+/// The circuit in the format of the puzzle input. Only the values of the wires which are not
+/// driven by a gate are written, so a simulated circuit is written like the parsed one.
+impl Display for Monitoring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut inputs: Vec<_> = self
+            .wires
+            .iter()
+            .filter(|(wire, _)| !CONSTANT_WIRES.contains(&wire.as_str()))
+            .filter(|(wire, _)| !self.circuit.gates.iter().any(|gate| &gate.output == *wire))
+            .filter_map(|(wire, value)| Some((wire, (*value)?)))
+            .collect();
+        inputs.sort();
+        for (wire, value) in inputs {
+            writeln!(f, "{}: {}", wire, value)?;
+        }
+        writeln!(f)?;
+        write!(f, "{}", self.circuit)
+    }
+}
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    type Input = Monitoring;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        Monitoring::new(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        match input.clone().simulate() {
+            Ok(result) => result.into(),
+            Err(_) => Answer::Unsolved,
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        match adder::find_swaps(&input.circuit.gates) {
+            Some(swaps) if !swaps.is_empty() => adder::format_swaps(&swaps).into(),
+            _ => Answer::Unsolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example0() {
+        let input_file = std::fs::read("example0.txt").unwrap();
+        let mut monitoring = Monitoring::new(&input_file).unwrap();
+        monitoring.simulate().unwrap();
+        assert_eq!(monitoring.wires.len(), 9);
+        assert_eq!(monitoring.result().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_example1() {
+        let input_file = std::fs::read("example1.txt").unwrap();
+        let mut monitoring = Monitoring::new(&input_file).unwrap();
+        monitoring.simulate().unwrap();
+        assert_eq!(monitoring.result().unwrap(), 2024);
+    }
+
+    #[test]
+    fn evaluate_circuit() {
+        let input_file = std::fs::read("example1.txt").unwrap();
+        let monitoring = Monitoring::new(&input_file).unwrap();
+        let (x, y) = (monitoring.number('x'), monitoring.number('y'));
+        assert_eq!((x, y), (0b01101, 0b11111));
+        assert_eq!(monitoring.circuit.evaluate(x, y), Some(2024));
+        // The example ANDs bit 0, XORs bit 1 and ORs bit 2 of the inputs.
+        let circuit = Monitoring::new(&std::fs::read("example0.txt").unwrap())
+            .unwrap()
+            .circuit;
+        assert_eq!(circuit.evaluate(0b011, 0b001), Some(0b011));
+        assert_eq!(circuit.evaluate(0, 0), Some(0));
+        assert_eq!(circuit.evaluate(u64::MAX, u64::MAX), Some(0b101));
+
+        let undriven = Monitoring::new(b"x00: 1\n\nx00 AND abc -> z00\n").unwrap();
+        assert_eq!(undriven.circuit.evaluate(1, 0), None);
+
+        let mut cyclic = Monitoring::new(b"x00: 1\n\nx00 AND z00 -> z00\n").unwrap();
+        assert_eq!(
+            cyclic.simulate(),
+            Err(NetlistError::Cycle {
+                wires: vec!["z00".to_string()]
+            })
+        );
+        assert_eq!(cyclic.result(), None);
+    }
+
+    #[test]
+    fn extended_gates() {
+        let input = "x00: 1\nx01: 0\n\nNOT x00 -> z00\nx00 NAND x01 -> z01\nx00 NOR x01 -> z02\n\
+                     x00 XNOR x01 -> z03\nx01 XOR 1 -> z04\nx00 AND 0 -> z05\n";
+        let mut monitoring = Monitoring::new(input.as_bytes()).unwrap();
+        assert_eq!(monitoring.simulate(), Ok(0b010010));
+        for (x00, x01, expected) in [(0, 0, 0b011111), (1, 0, 0b010010), (1, 1, 0b001000)] {
+            let input = format!("x00: {}\nx01: {}\n\n{}", x00, x01, monitoring.circuit);
+            assert_eq!(
+                Monitoring::new(input.as_bytes()).unwrap().simulate(),
+                Ok(expected)
+            );
+        }
+        assert_eq!(monitoring.circuit.gates[0].to_string(), "NOT x00 -> z00");
+        assert_eq!(monitoring.circuit.gates[4].to_string(), "x01 XOR 1 -> z04");
+
+        let error = |input: &str| Monitoring::new(input.as_bytes()).unwrap_err().to_string();
+        assert_eq!(error("AND x00 -> z00"), "1:1: expected NOT, found \"AND\"");
+        assert_eq!(
+            error("x00 NOT x01 -> z00"),
+            "1:5: expected AND, OR, XOR, NAND, NOR or XNOR, found \"NOT\""
+        );
+        assert_eq!(
+            error("x00 OR x01 -> 1"),
+            "1:15: constant wire \"1\" can not be the output of a gate"
+        );
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let error = |input: &str| Monitoring::new(input.as_bytes()).unwrap_err().to_string();
+        assert_eq!(
+            error("x00 AND y00 -> z00 junk"),
+            "1:1: expected an initial wire value or a gate"
+        );
+        assert_eq!(
+            error("junk x00 AND y00 -> z00"),
+            "1:1: expected an initial wire value or a gate"
+        );
+        assert_eq!(
+            error("x 00: 1"),
+            "1:1: expected a wire name, found \"x 00\""
+        );
+        assert_eq!(
+            error("x64: 1"),
+            "1:1: wire \"x64\" is past bit 63 of a 64-bit number"
+        );
+        assert_eq!(
+            error("x00 AND y00 -> z64"),
+            "1:16: wire \"z64\" is past bit 63 of a 64-bit number"
+        );
+
+        // Internal wires may start with x, y or z.
+        let mut monitoring = Monitoring::new(
+            b"x00: 1\ny00: 0\n\nx00 AND y00 -> fzq\nfzq OR x00 -> zbk\nzbk XOR y00 -> z01\n\
+            x00 XOR y00 -> z00\n",
+        )
+        .unwrap();
+        assert_eq!(monitoring.z_vars.len(), 2);
+        assert_eq!(monitoring.simulate(), Ok(0b11));
+        assert_eq!(monitoring.number('z'), 0b11);
+        assert_eq!(monitoring.number('x'), 1);
+    }
+
+    #[test]
+    fn write_input() {
+        let input_file = std::fs::read("example1.txt").unwrap();
+        let mut monitoring = Monitoring::new(&input_file).unwrap();
+        let written = monitoring.to_string();
+        assert!(written.starts_with("x00: 1\nx01: 0\n"));
+        assert!(written.contains("y04: 1\n\nntg XOR fgs -> mjb\n"));
+        monitoring.simulate().unwrap();
+        assert_eq!(monitoring.to_string(), written);
+        let mut reparsed = Monitoring::new(written.as_bytes()).unwrap();
+        assert_eq!(reparsed.circuit, monitoring.circuit);
+        assert_eq!(reparsed.simulate(), Ok(2024));
+    }
+}
@@ -0,0 +1,401 @@
+//! Gate circuit compiled for evaluation. Wire names are interned into dense ids, and the gates
+//! are sorted so that every gate comes after the gates which drive its inputs. Evaluating the
+//! circuit is then a single pass over the gates, without recursion and without looking up names.
+//!
+//! [Netlist::evaluate_batch] packs 64 pairs of input numbers into one `u64` per wire, where bit
+//! `j` of every wire belongs to pair `j`. Every gate then evaluates all pairs with one bitwise
+//! operation.
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{bit_index, Circuit, Monitoring, Operator, CONSTANT_WIRES};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetlistError {
+    /// These wires are on a loop of gates or depend on one, sorted by name.
+    Cycle { wires: Vec<String> },
+    /// The wire is driven by more than one gate.
+    MultipleDrivers { wire: String },
+    /// The wire is neither driven by a gate nor has an input value.
+    Undriven { wire: String },
+}
+
+impl Display for NetlistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetlistError::Cycle { wires } => {
+                write!(f, "wires {} are on or behind a cycle", wires.join(", "))
+            }
+            NetlistError::MultipleDrivers { wire } => {
+                write!(f, "wire {} is driven by more than one gate", wire)
+            }
+            NetlistError::Undriven { wire } => {
+                write!(f, "wire {} is neither driven by a gate nor an input", wire)
+            }
+        }
+    }
+}
+
+pub type WireId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetGate {
+    pub inputs: [WireId; 2],
+    pub operator: Operator,
+    pub output: WireId,
+}
+
+/// Transposes a matrix of 64 by 64 bits, so bit `j` of word `i` becomes bit `i` of word `j`.
+/// Each round swaps the off-diagonal blocks of all blocks of the previous round.
+fn transpose(matrix: &mut [u64; 64]) {
+    let mut width = 32;
+    let mut mask = 0x0000_0000_ffff_ffff;
+    while width != 0 {
+        for start in (0..64).step_by(2 * width) {
+            for idx in start..start + width {
+                let swapped = ((matrix[idx] >> width) ^ matrix[idx + width]) & mask;
+                matrix[idx] ^= swapped << width;
+                matrix[idx + width] ^= swapped;
+            }
+        }
+        width >>= 1;
+        mask ^= mask << width;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Netlist {
+    names: Vec<String>,
+    ids: HashMap<String, WireId>,
+    /// Wires which are not driven by a gate, sorted by name.
+    inputs: Vec<WireId>,
+    /// Gates in topological order.
+    gates: Vec<NetGate>,
+    /// The constant wire with the value 1, if it is used. The one with the value 0 needs no
+    /// special treatment.
+    one: Option<WireId>,
+    /// Bit index and wire of the `x`, `y` and `z` wires.
+    x_bits: Vec<(u32, WireId)>,
+    y_bits: Vec<(u32, WireId)>,
+    z_bits: Vec<(u32, WireId)>,
+    /// An input wire which is not an `x` or `y` wire, so it has no value in a batch.
+    other_input: Option<WireId>,
+}
+
+impl Netlist {
+    pub fn new(circuit: &Circuit) -> Result<Self, NetlistError> {
+        let mut names = Vec::new();
+        let mut ids = HashMap::new();
+        let mut intern = |name: &str| {
+            *ids.entry(name.to_string()).or_insert_with(|| {
+                names.push(name.to_string());
+                names.len() - 1
+            })
+        };
+        let gates: Vec<NetGate> = circuit
+            .gates
+            .iter()
+            .map(|gate| NetGate {
+                inputs: gate.inputs.each_ref().map(|wire| intern(wire)),
+                operator: gate.operator,
+                output: intern(&gate.output),
+            })
+            .collect();
+
+        let mut driver = vec![None; names.len()];
+        let mut readers = vec![Vec::new(); names.len()];
+        for (idx, gate) in gates.iter().enumerate() {
+            if driver[gate.output].replace(idx).is_some() {
+                return Err(NetlistError::MultipleDrivers {
+                    wire: names[gate.output].clone(),
+                });
+            }
+            for &input in &gate.inputs {
+                readers[input].push(idx);
+            }
+        }
+        // Kahn's algorithm: a gate is ready once all of its inputs are driven by sorted gates.
+        let mut missing: Vec<usize> = gates
+            .iter()
+            .map(|gate| {
+                gate.inputs
+                    .iter()
+                    .filter(|&&input| driver[input].is_some())
+                    .count()
+            })
+            .collect();
+        let mut ready: Vec<usize> = (0..gates.len()).filter(|&idx| missing[idx] == 0).collect();
+        let mut order = Vec::with_capacity(gates.len());
+        while let Some(idx) = ready.pop() {
+            order.push(gates[idx]);
+            for &reader in &readers[gates[idx].output] {
+                missing[reader] -= 1;
+                if missing[reader] == 0 {
+                    ready.push(reader);
+                }
+            }
+        }
+        if order.len() < gates.len() {
+            let mut wires: Vec<String> = (0..gates.len())
+                .filter(|&idx| missing[idx] > 0)
+                .map(|idx| names[gates[idx].output].clone())
+                .collect();
+            wires.sort();
+            return Err(NetlistError::Cycle { wires });
+        }
+
+        let mut inputs: Vec<WireId> = (0..names.len())
+            .filter(|&id| driver[id].is_none() && !CONSTANT_WIRES.contains(&names[id].as_str()))
+            .collect();
+        inputs.sort_by(|&left, &right| names[left].cmp(&names[right]));
+        let bits = |prefix: char, wires: &[WireId]| -> Vec<(u32, WireId)> {
+            wires
+                .iter()
+                .filter_map(|&id| Some((bit_index(&names[id], prefix)?, id)))
+                .collect()
+        };
+        let x_bits = bits('x', &inputs);
+        let y_bits = bits('y', &inputs);
+        let z_bits = bits('z', &Vec::from_iter(0..names.len()));
+        let other_input = inputs
+            .iter()
+            .copied()
+            .find(|&id| !x_bits.iter().chain(&y_bits).any(|&(_, input)| input == id));
+        let one = ids.get(CONSTANT_WIRES[1]).copied();
+        Ok(Self {
+            names,
+            ids,
+            inputs,
+            gates: order,
+            one,
+            x_bits,
+            y_bits,
+            z_bits,
+            other_input,
+        })
+    }
+
+    pub fn id(&self, name: &str) -> Option<WireId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: WireId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn wire_count(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Wires which are not driven by a gate and are not constant, sorted by name.
+    pub fn inputs(&self) -> &[WireId] {
+        &self.inputs
+    }
+
+    /// Gates in topological order.
+    pub fn gates(&self) -> &[NetGate] {
+        &self.gates
+    }
+
+    /// Number of bits of the numbers on the wires starting with `prefix`.
+    pub fn width(&self, prefix: char) -> u32 {
+        let bits = match prefix {
+            'x' => &self.x_bits,
+            'y' => &self.y_bits,
+            'z' => &self.z_bits,
+            _ => return 0,
+        };
+        bits.iter().map(|&(idx, _)| idx + 1).max().unwrap_or(0)
+    }
+
+    /// Evaluates the gates in order, once the input wires have their values. Every bit of the
+    /// values is a separate evaluation.
+    fn propagate(&self, values: &mut [u64]) {
+        if let Some(one) = self.one {
+            values[one] = u64::MAX;
+        }
+        for gate in &self.gates {
+            let [left, right] = gate.inputs.map(|input| values[input]);
+            values[gate.output] = Monitoring::resolve_wire(left, right, gate.operator);
+        }
+    }
+
+    /// Values of all wires, indexed by [WireId]. `input` gives the values of the wires which are
+    /// not driven by a gate.
+    pub fn evaluate_wires(
+        &self,
+        input: impl Fn(&str) -> Option<u8>,
+    ) -> Result<Vec<u8>, NetlistError> {
+        let mut values = vec![0; self.names.len()];
+        for &id in &self.inputs {
+            let value = input(&self.names[id]).ok_or_else(|| NetlistError::Undriven {
+                wire: self.names[id].clone(),
+            })?;
+            values[id] = 0u64.wrapping_sub(value as u64 & 1);
+        }
+        self.propagate(&mut values);
+        Ok(values.into_iter().map(|value| (value & 1) as u8).collect())
+    }
+
+    /// Evaluates up to 64 pairs of `x` and `y` numbers at once and returns the number on the `z`
+    /// wires for each pair, like [Netlist::evaluate].
+    ///
+    /// # Panics
+    ///
+    /// If there are more than 64 pairs.
+    pub fn evaluate_batch(&self, pairs: &[(u64, u64)]) -> Result<Vec<u64>, NetlistError> {
+        assert!(pairs.len() <= 64, "a batch holds at most 64 pairs");
+        if let Some(id) = self.other_input {
+            return Err(NetlistError::Undriven {
+                wire: self.names[id].clone(),
+            });
+        }
+        let mut x = [0; 64];
+        let mut y = [0; 64];
+        for (lane, &pair) in pairs.iter().enumerate() {
+            (x[lane], y[lane]) = pair;
+        }
+        transpose(&mut x);
+        transpose(&mut y);
+        let mut values = vec![0; self.names.len()];
+        for (bits, lanes) in [(&self.x_bits, &x), (&self.y_bits, &y)] {
+            for &(idx, id) in bits {
+                values[id] = lanes[idx as usize];
+            }
+        }
+        self.propagate(&mut values);
+        let mut z = [0; 64];
+        for &(idx, id) in &self.z_bits {
+            z[idx as usize] = values[id];
+        }
+        transpose(&mut z);
+        Ok(z[..pairs.len()].to_vec())
+    }
+
+    /// The number on the wires starting with `prefix`.
+    pub fn number(&self, values: &[u8], prefix: char) -> u64 {
+        let mut number = 0;
+        for (name, &value) in self.names.iter().zip(values) {
+            if let Some(idx) = bit_index(name, prefix) {
+                number |= (value as u64) << idx;
+            }
+        }
+        number
+    }
+
+    /// Sets the `x` and `y` wires to the bits of the numbers and returns the number on the `z`
+    /// wires. Bits without a wire are ignored.
+    pub fn evaluate(&self, x: u64, y: u64) -> Result<u64, NetlistError> {
+        Ok(self.evaluate_batch(&[(x, y)])?[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn netlist(gates: &str) -> Result<Netlist, NetlistError> {
+        Netlist::new(&Monitoring::new(gates.as_bytes()).unwrap().circuit)
+    }
+
+    #[test]
+    fn topological_order() {
+        let netlist = netlist("c AND d -> z00\nx00 XOR y00 -> c\ny00 OR x00 -> d\n").unwrap();
+        let order: Vec<&str> = netlist
+            .gates()
+            .iter()
+            .map(|gate| netlist.name(gate.output))
+            .collect();
+        assert_eq!(order.last(), Some(&"z00"));
+        let inputs: Vec<&str> = netlist
+            .inputs()
+            .iter()
+            .map(|&id| netlist.name(id))
+            .collect();
+        assert_eq!(inputs, ["x00", "y00"]);
+        assert_eq!(netlist.evaluate(1, 0), Ok(1));
+        assert_eq!(netlist.evaluate(1, 1), Ok(0));
+    }
+
+    #[test]
+    fn long_chain() {
+        // Each gate flips the value, which is deep enough to overflow a recursive evaluation.
+        let mut gates = "x00 XOR y00 -> w0\n".to_string();
+        for idx in 0..20_000 {
+            gates += &format!("w{} XOR x00 -> w{}\n", idx, idx + 1);
+        }
+        gates += "w20000 AND x00 -> z00\n";
+        let netlist = netlist(&gates).unwrap();
+        assert_eq!(netlist.wire_count(), 20_004);
+        assert_eq!(netlist.evaluate(1, 0), Ok(1));
+        assert_eq!(netlist.evaluate(1, 1), Ok(0));
+    }
+
+    #[test]
+    fn transposed_matrix() {
+        let mut matrix: [u64; 64] = std::array::from_fn(|idx| (idx as u64 + 1) * 0x9e37_79b9);
+        let original = matrix;
+        transpose(&mut matrix);
+        for (row, word) in matrix.iter().enumerate() {
+            for (column, original) in original.iter().enumerate() {
+                assert_eq!(word >> column & 1, original >> row & 1);
+            }
+        }
+    }
+
+    #[test]
+    fn batches() {
+        let input_file = std::fs::read("example1.txt").unwrap();
+        let example = Netlist::new(&Monitoring::new(&input_file).unwrap().circuit).unwrap();
+        assert_eq!(example.width('x'), 5);
+        assert_eq!(example.width('z'), 13);
+        let pairs: Vec<(u64, u64)> = (0..64).map(|idx| (idx % 32, (idx * 7) % 32)).collect();
+        let z = example.evaluate_batch(&pairs).unwrap();
+        for (&(x, y), z) in pairs.iter().zip(z) {
+            assert_eq!(example.evaluate(x, y), Ok(z));
+        }
+        assert_eq!(example.evaluate_batch(&pairs[..3]).unwrap().len(), 3);
+        assert_eq!(example.evaluate(0b01101, 0b11111), Ok(2024));
+
+        let inverter = netlist("x00 XOR 1 -> z00\nNOT x01 -> z01\n").unwrap();
+        assert_eq!(inverter.inputs().len(), 2);
+        assert_eq!(
+            inverter.evaluate_batch(&[(0b00, 0), (0b01, 0), (0b11, 0)]),
+            Ok(vec![0b11, 0b10, 0b00])
+        );
+    }
+
+    #[test]
+    fn invalid_netlists() {
+        let error = netlist("x00 AND b -> a\na OR y00 -> b\nb XOR x00 -> z00\n").unwrap_err();
+        assert_eq!(
+            error,
+            NetlistError::Cycle {
+                wires: vec!["a".to_string(), "b".to_string(), "z00".to_string()]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "wires a, b, z00 are on or behind a cycle"
+        );
+        assert_eq!(
+            netlist("x00 AND y00 -> z00\nx00 OR y00 -> z00\n").unwrap_err(),
+            NetlistError::MultipleDrivers {
+                wire: "z00".to_string()
+            }
+        );
+        // Wires past bit 63 are rejected by the parser, and ignored if a circuit is built directly.
+        let mut wide = Monitoring::new(b"x00 XOR y00 -> z00\n").unwrap().circuit;
+        wide.gates[0].output = "z64".to_string();
+        let wide = Netlist::new(&wide).unwrap();
+        assert_eq!(wide.width('z'), 0);
+        assert_eq!(wide.evaluate(1, 0), Ok(0));
+        assert_eq!(wide.number(&[1, 1, 1], 'z'), 0);
+        let undriven = netlist("x00 AND abc -> z00\n").unwrap();
+        assert_eq!(
+            undriven.evaluate(1, 1),
+            Err(NetlistError::Undriven {
+                wire: "abc".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use aoc_common::{input_lines, Answer, ParseError, Solver};
+
+pub type KeyLockType = [usize; 5];
+pub type KeyLockList = Vec<KeyLockType>;
+
+#[derive(Debug, Default, Eq, PartialEq, Hash)]
+pub struct Key([usize; 5]);
+#[derive(Debug, Default, Eq, PartialEq, Hash)]
+pub struct Lock([usize; 5]);
+
+#[derive(Debug, Default, Eq, PartialEq, Hash)]
+pub struct Combination {
+    key: Key,
+    lock: Lock,
+}
+
+pub fn parse_keys_and_locks(input: &[u8]) -> Result<(KeyLockList, KeyLockList), ParseError> {
+    let mut keys = Vec::new();
+    let mut locks = Vec::new();
+    let mut next_fill_level_from_top = [0_usize; 5];
+    let mut next_is_key = true;
+    let mut row_idx = 0;
+    let num_of_lines = input_lines(input)?.count();
+    for line in input_lines(input)? {
+        if line.text.len() > 5 {
+            return Err(line.error(6, "expected a schematic with 5 columns"));
+        }
+        if let Some(idx) = line.text.find(|c| c != '#' && c != '.') {
+            return Err(line.unexpected(idx, "schematic"));
+        }
+        if line.text.is_empty() || line.number == num_of_lines {
+            if !next_is_key {
+                locks.push(next_fill_level_from_top);
+            } else {
+                next_fill_level_from_top = next_fill_level_from_top.map(|v| 5 - v);
+                keys.push(next_fill_level_from_top);
+            }
+            row_idx = 0;
+            next_fill_level_from_top = [0; 5];
+            continue;
+        }
+        if row_idx == 0 {
+            next_is_key = !line.text.contains("#");
+            row_idx += 1;
+            continue;
+        }
+        for (col_idx, char) in line.text.chars().enumerate() {
+            if next_is_key && char == '.' {
+                next_fill_level_from_top[col_idx] += 1;
+            }
+            if !next_is_key && char == '#' {
+                next_fill_level_from_top[col_idx] += 1;
+            }
+        }
+        row_idx += 1;
+    }
+    Ok((keys, locks))
+}
+
+pub fn count_fitting_combinations(keys: KeyLockList, locks: KeyLockList) -> usize {
+    let mut fitting_combination = 0;
+    let mut combinations = HashSet::new();
+    for key in &keys {
+        for lock in &locks {
+            combinations.insert((key, lock));
+            let mut skip = false;
+            for (&key_val, &lock_val) in key.iter().zip(lock.iter()) {
+                if key_val + lock_val > 5 {
+                    skip = true;
+                    break;
+                }
+            }
+            if skip {
+                continue;
+            }
+            fitting_combination += 1;
+        }
+    }
+    fitting_combination
+}
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    type Input = (KeyLockList, KeyLockList);
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_keys_and_locks(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_fitting_combinations(input.0.clone(), input.1.clone()).into()
+    }
+
+    fn part2(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input_file = std::fs::read("example.txt").unwrap();
+        let (keys, locks) = parse_keys_and_locks(&input_file).unwrap();
+        let fitting_combination = count_fitting_combinations(keys, locks);
+        assert_eq!(fitting_combination, 3);
+    }
+}
@@ -0,0 +1,53 @@
+use aoc_common::{input_text, Answer, ParseError, Solver};
+use regex::Regex;
+
+/// Scans the corrupted memory for multiplications. Returns the sum of all multiplications and
+/// the sum of the multiplications which are enabled by the `do()` and `don't()` instructions.
+pub fn scan_memory(memory: &str) -> (u32, u32) {
+    let re = Regex::new(r"do\(\)|don't\(\)|mul\((\d+),(\d+)\)").unwrap();
+    let mut enabled = true;
+    let mut sum_part1 = 0;
+    let mut sum_part2 = 0;
+    for captures in re.captures_iter(memory) {
+        match captures.get(0).unwrap().as_str() {
+            "do()" => enabled = true,
+            "don't()" => enabled = false,
+            cmd if cmd.starts_with("mul") => {
+                // Extract the numbers from the capture groups
+                let a = captures
+                    .get(1)
+                    .and_then(|m| m.as_str().parse::<u32>().ok())
+                    .expect("Failed to parse first operand");
+                let b = captures
+                    .get(2)
+                    .and_then(|m| m.as_str().parse::<u32>().ok())
+                    .expect("Failed to parse second operand");
+                let increment = a * b;
+                sum_part1 += increment;
+                if enabled {
+                    sum_part2 += increment;
+                }
+            }
+            _ => continue,
+        }
+    }
+    (sum_part1, sum_part2)
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = String;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        input_text(data).map(str::to_string)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        scan_memory(input).0.into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        scan_memory(input).1.into()
+    }
+}
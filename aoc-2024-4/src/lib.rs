@@ -0,0 +1,172 @@
+use aoc_common::{Answer, ParseError, Solver};
+use aoc_grid::Grid;
+
+#[derive(Debug, Clone)]
+pub struct XmasMatrix {
+    m: Grid<u8>,
+    num_rows: usize,
+    num_cols: usize,
+    xmas_count: usize,
+    xmas_count_p2: usize,
+}
+
+impl XmasMatrix {
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        let m = Grid::parse(data, "word search", b"XMAS.")?;
+        let num_rows = m.rows();
+        let num_cols = m.cols();
+        Ok(Self {
+            m,
+            num_rows,
+            num_cols,
+            xmas_count: 0,
+            xmas_count_p2: 0,
+        })
+    }
+
+    /// Returns the XMAS count for part 1 and part 2.
+    pub fn check(&mut self) -> (usize, usize) {
+        self.xmas_count = 0;
+        self.xmas_count_p2 = 0;
+        for i in 0..self.num_rows {
+            for j in 0..self.num_cols {
+                self.part1(i, j);
+                self.part2(i, j);
+            }
+        }
+        (self.xmas_count, self.xmas_count_p2)
+    }
+
+    fn part1(&mut self, i: usize, j: usize) {
+        if self.enough_space_in_east_dir_p1(j) {
+            if self.east_check_p1(i, j) {
+                self.xmas_count += 1;
+            }
+            if self.enough_space_in_south_dir_p1(i) && self.southeast_check_p1(i, j) {
+                self.xmas_count += 1;
+            }
+            if self.enough_space_in_north_dir_p1(i) && self.northeast_check_p1(i, j) {
+                self.xmas_count += 1;
+            }
+        }
+        if self.enough_space_in_west_dir_p1(j) {
+            if self.west_check_p1(i, j) {
+                self.xmas_count += 1;
+            }
+            if self.enough_space_in_south_dir_p1(i) && self.southwest_check_p1(i, j) {
+                self.xmas_count += 1;
+            }
+            if self.enough_space_in_north_dir_p1(i) && self.northwest_check_p1(i, j) {
+                self.xmas_count += 1;
+            }
+        }
+        if self.enough_space_in_north_dir_p1(i) && self.north_check_p1(i, j) {
+            self.xmas_count += 1;
+        }
+        if self.enough_space_in_south_dir_p1(i) && self.south_check_p1(i, j) {
+            self.xmas_count += 1;
+        }
+    }
+
+    fn enough_space_in_east_dir_p1(&self, j: usize) -> bool {
+        j < self.num_cols - 3
+    }
+    fn enough_space_in_south_dir_p1(&self, i: usize) -> bool {
+        i < self.num_rows - 3
+    }
+    fn enough_space_in_west_dir_p1(&self, j: usize) -> bool {
+        j >= 3
+    }
+    fn enough_space_in_north_dir_p1(&self, i: usize) -> bool {
+        i >= 3
+    }
+
+    fn east_check_p1(&self, i: usize, j: usize) -> bool {
+        self.m[(i, j)] == b'X'
+            && self.m[(i, j + 1)] == b'M'
+            && self.m[(i, j + 2)] == b'A'
+            && self.m[(i, j + 3)] == b'S'
+    }
+
+    fn southeast_check_p1(&self, i: usize, j: usize) -> bool {
+        self.m[(i, j)] == b'X'
+            && self.m[(i + 1, j + 1)] == b'M'
+            && self.m[(i + 2, j + 2)] == b'A'
+            && self.m[(i + 3, j + 3)] == b'S'
+    }
+
+    fn south_check_p1(&self, i: usize, j: usize) -> bool {
+        self.m[(i, j)] == b'X'
+            && self.m[(i + 1, j)] == b'M'
+            && self.m[(i + 2, j)] == b'A'
+            && self.m[(i + 3, j)] == b'S'
+    }
+
+    fn southwest_check_p1(&self, i: usize, j: usize) -> bool {
+        self.m[(i, j)] == b'X'
+            && self.m[(i + 1, j - 1)] == b'M'
+            && self.m[(i + 2, j - 2)] == b'A'
+            && self.m[(i + 3, j - 3)] == b'S'
+    }
+
+    fn west_check_p1(&self, i: usize, j: usize) -> bool {
+        self.m[(i, j)] == b'X'
+            && self.m[(i, j - 1)] == b'M'
+            && self.m[(i, j - 2)] == b'A'
+            && self.m[(i, j - 3)] == b'S'
+    }
+
+    fn northwest_check_p1(&self, i: usize, j: usize) -> bool {
+        self.m[(i, j)] == b'X'
+            && self.m[(i - 1, j - 1)] == b'M'
+            && self.m[(i - 2, j - 2)] == b'A'
+            && self.m[(i - 3, j - 3)] == b'S'
+    }
+
+    fn north_check_p1(&self, i: usize, j: usize) -> bool {
+        self.m[(i, j)] == b'X'
+            && self.m[(i - 1, j)] == b'M'
+            && self.m[(i - 2, j)] == b'A'
+            && self.m[(i - 3, j)] == b'S'
+    }
+    fn northeast_check_p1(&self, i: usize, j: usize) -> bool {
+        self.m[(i, j)] == b'X'
+            && self.m[(i - 1, j + 1)] == b'M'
+            && self.m[(i - 2, j + 2)] == b'A'
+            && self.m[(i - 3, j + 3)] == b'S'
+    }
+
+    fn enough_surrounding_space(&self, i: usize, j: usize) -> bool {
+        j < self.num_cols - 1 && j >= 1 && i >= 1 && i < self.num_rows - 1
+    }
+
+    fn part2(&mut self, i: usize, j: usize) {
+        if self.m[(i, j)] == b'A'
+            && self.enough_surrounding_space(i, j)
+            && ((self.m[(i + 1, j + 1)] == b'M' && self.m[(i - 1, j - 1)] == b'S')
+                || (self.m[(i + 1, j + 1)] == b'S' && self.m[(i - 1, j - 1)] == b'M'))
+            && ((self.m[(i + 1, j - 1)] == b'M' && self.m[(i - 1, j + 1)] == b'S')
+                || (self.m[(i + 1, j - 1)] == b'S' && self.m[(i - 1, j + 1)] == b'M'))
+        {
+            self.xmas_count_p2 += 1;
+        }
+    }
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input = XmasMatrix;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        XmasMatrix::new(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.clone().check().0.into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.clone().check().1.into()
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::{input_lines, Answer, ParseError, Solver};
+
+pub type NumbersThatMustComeAfter = Vec<u32>;
+pub type RuleMap = HashMap<u32, NumbersThatMustComeAfter>;
+
+const DEBUG_P1: bool = false;
+const DEBUG_P2: bool = false;
+
+#[derive(Debug, Default, Clone)]
+pub struct PrintQueue {
+    pub rule_map: RuleMap,
+    pub page_sets_to_produce: Vec<Vec<u32>>,
+}
+
+impl PrintQueue {
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        let mut rule_map = RuleMap::default();
+        let mut page_sets_to_produce = Vec::new();
+        for line in input_lines(data)? {
+            if line.text.is_empty() {
+                continue;
+            }
+            if line.text.contains('|') {
+                let (before, after) = line.split_once("|")?;
+                rule_map
+                    .entry(line.parse::<u32>(before, "a page number")?)
+                    .or_insert_with(Vec::new)
+                    .push(line.parse::<u32>(after, "a page number")?);
+                continue;
+            }
+            let next_page = line
+                .text
+                .split(',')
+                .map(|s| line.parse::<u32>(s, "a page number"))
+                .collect::<Result<Vec<u32>, ParseError>>()?;
+            if next_page.len() < 2 {
+                return Err(line.error(0, "expected an ordering rule or at least two pages"));
+            }
+            page_sets_to_produce.push(next_page);
+        }
+        Ok(Self {
+            rule_map,
+            page_sets_to_produce,
+        })
+    }
+
+    /// Returns the sum of the middle page numbers of all correctly ordered page sets and the list
+    /// of incorrectly ordered page sets.
+    pub fn check_ordering(&self) -> (u32, Vec<Vec<u32>>) {
+        let mut mid_page_nums_added = 0;
+        let mut invalid_page_sets = Vec::new();
+        for page_set in &self.page_sets_to_produce {
+            let mut correctly_ordered = true;
+            let mut finished_pages = HashSet::new();
+            assert!(page_set.len() > 1);
+            finished_pages.insert(page_set[0]);
+            for page in page_set.iter().skip(1) {
+                if let Some(numbers_must_come_after) = self.rule_map.get(page) {
+                    for number in numbers_must_come_after {
+                        if finished_pages.contains(number) {
+                            if DEBUG_P1 {
+                                println!("Page set {:?} is not ordered correctly", page_set);
+                                println!(
+                                "Number {} which should come after number {} is in finished set {:?}",
+                                number,
+                                page,
+                                finished_pages
+                            );
+                            }
+                            correctly_ordered = false;
+                            break;
+                        }
+                    }
+                    if !correctly_ordered {
+                        invalid_page_sets.push(page_set.clone());
+                        break;
+                    }
+                }
+
+                finished_pages.insert(*page);
+            }
+            assert_ne!(page_set.len() % 2, 0);
+            if correctly_ordered {
+                if DEBUG_P1 {
+                    println!("Page set {:?} is ordered correctly", page_set);
+                    println!("Added number {}", page_set[page_set.len() / 2]);
+                }
+                mid_page_nums_added += page_set[page_set.len() / 2];
+            }
+        }
+        (mid_page_nums_added, invalid_page_sets)
+    }
+
+    /// Re-orders the given invalid page sets and returns the sum of their middle page numbers.
+    pub fn reorder(&self, invalid_page_sets: &mut [Vec<u32>]) -> u32 {
+        if DEBUG_P2 {
+            println!("Invalid page sets:");
+        }
+        let mut mid_page_nums_added = 0;
+        for page_set in invalid_page_sets.iter_mut() {
+            if DEBUG_P2 {
+                println!("Invalid page set: {:?}", page_set);
+            }
+            loop {
+                let mut finished_pages = Vec::new();
+                let mut values_to_push_back = Vec::new();
+                for page in page_set.iter() {
+                    if let Some(numbers_must_come_after) = self.rule_map.get(page) {
+                        for number in numbers_must_come_after {
+                            for finished_page in finished_pages.iter() {
+                                if finished_page == number {
+                                    values_to_push_back.push(*finished_page);
+                                }
+                            }
+                        }
+                        if !values_to_push_back.is_empty() {
+                            break;
+                        }
+                    }
+                    finished_pages.push(*page);
+                }
+                // We are done, nothing to re-order
+                if values_to_push_back.is_empty() {
+                    if DEBUG_P2 {
+                        println!("Ordered: {:?}", page_set);
+                    }
+                    mid_page_nums_added += page_set[page_set.len() / 2];
+                    break;
+                }
+                // We push back the values that should come after a certain number and then re-run
+                // the algorithm from the start.
+                page_set.retain(|val| !values_to_push_back.contains(val));
+                for value in values_to_push_back {
+                    page_set.push(value);
+                }
+            }
+        }
+        mid_page_nums_added
+    }
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input = PrintQueue;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        PrintQueue::new(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.check_ordering().0.into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        let (_, mut invalid_page_sets) = input.check_ordering();
+        input.reorder(&mut invalid_page_sets).into()
+    }
+}
@@ -0,0 +1,304 @@
+use aoc_common::{Answer, ParseError, Solver};
+use aoc_grid::{Coord2D, Direction, Grid};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const DEBUG_P2: bool = false;
+
+const VISIT_DEBUG: bool = false;
+
+pub type PointSet = HashSet<Coord2D>;
+
+#[derive(Debug, Clone)]
+pub struct Visited(pub HashMap<Coord2D, HashSet<Direction>>);
+
+#[derive(Debug)]
+pub struct PathLoop;
+
+impl std::fmt::Display for PathLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the guard walks in a loop and never leaves the lab")
+    }
+}
+
+impl std::error::Error for PathLoop {}
+
+impl Visited {
+    pub fn visited_places(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn insert(&mut self, coord: Coord2D, dir: Direction) -> Result<(), PathLoop> {
+        if VISIT_DEBUG {
+            println!(
+                "Visited: {:?} (file coord x {} y {})",
+                coord,
+                coord.x + 1,
+                coord.y + 1
+            );
+        }
+        match self.0.entry(coord) {
+            std::collections::hash_map::Entry::Occupied(mut occupied_entry) => {
+                if occupied_entry.get().contains(&dir) {
+                    return Err(PathLoop);
+                }
+                occupied_entry.get_mut().insert(dir);
+            }
+            std::collections::hash_map::Entry::Vacant(vacant_entry) => {
+                let mut new_set = HashSet::default();
+                new_set.insert(dir);
+                vacant_entry.insert(new_set);
+            }
+        };
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Guard {
+    pub coord: Coord2D,
+    pub dir: Direction,
+}
+
+impl Guard {
+    pub fn update_coord(&mut self, new_coord: Coord2D) {
+        self.coord = new_coord;
+    }
+
+    pub fn turn(&mut self) {
+        // A turn is always a 90 degrees turn clockwise.
+        self.dir = self.dir.cw();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Lab {
+    pub x_dim: usize,
+    pub y_dim: usize,
+    pub guard_origin: Coord2D,
+    pub obstacles: PointSet,
+    pub additional_obstacle: Option<Coord2D>,
+    visited: Visited,
+    pub guard: Guard,
+    iterations: u32,
+}
+
+impl Lab {
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        let grid = Grid::parse(data, "lab", b"#.^")?;
+        let obstacles = grid.find_all(b'#').collect();
+        let guard_coord = grid.find_required(b'^', "lab")?;
+        let visited = Visited(HashMap::default());
+
+        Ok(Self {
+            x_dim: grid.rows(),
+            y_dim: grid.cols(),
+            guard_origin: guard_coord,
+            obstacles,
+            visited,
+            iterations: 0,
+            additional_obstacle: None,
+            guard: Guard::default(),
+        })
+    }
+
+    pub fn update_additional_obstacle(&mut self, coord: Coord2D) {
+        if let Some(coord) = self.additional_obstacle.take() {
+            self.obstacles.remove(&coord);
+        }
+        self.additional_obstacle = Some(coord);
+        self.obstacles.insert(coord);
+    }
+
+    pub fn patrol(&mut self) -> Result<Visited, PathLoop> {
+        self.visited.0.clear();
+        self.guard.coord = self.guard_origin;
+        self.guard.dir = Direction::North;
+        self.iterations = 0;
+        let mut reached_edge = false;
+        while !reached_edge {
+            reached_edge = true;
+            if self.iterations >= 10000 {
+                println!("Additional obstacle: {:?}", self.additional_obstacle);
+                panic!("infinite loop detected");
+            }
+            match self.guard.dir {
+                Direction::North => {
+                    for x in (0..=self.guard.coord.x).rev() {
+                        if self.obstacles.contains(&Coord2D {
+                            x,
+                            y: self.guard.coord.y,
+                        }) {
+                            let new_coord = Coord2D {
+                                x: x + 1,
+                                y: self.guard.coord.y,
+                            };
+                            self.guard.update_coord(new_coord);
+                            self.guard.turn();
+                            reached_edge = false;
+                            break;
+                        }
+                        self.visited.insert(
+                            Coord2D {
+                                x,
+                                y: self.guard.coord.y,
+                            },
+                            self.guard.dir,
+                        )?;
+                    }
+                    // We reached the upper edge and can walk out of the map.
+                }
+                Direction::South => {
+                    for x in self.guard.coord.x..self.x_dim {
+                        if self.obstacles.contains(&Coord2D {
+                            x,
+                            y: self.guard.coord.y,
+                        }) {
+                            let new_coord = Coord2D {
+                                x: x - 1,
+                                y: self.guard.coord.y,
+                            };
+                            self.guard.update_coord(new_coord);
+                            self.guard.turn();
+                            reached_edge = false;
+                            break;
+                        }
+                        self.visited.insert(
+                            Coord2D {
+                                x,
+                                y: self.guard.coord.y,
+                            },
+                            self.guard.dir,
+                        )?;
+                    }
+                    // We reached the lower edge and can walk out of the map.
+                }
+                Direction::West => {
+                    for y in (0..=self.guard.coord.y).rev() {
+                        if self.obstacles.contains(&Coord2D {
+                            x: self.guard.coord.x,
+                            y,
+                        }) {
+                            let new_coord = Coord2D {
+                                x: self.guard.coord.x,
+                                y: y + 1,
+                            };
+                            self.guard.update_coord(new_coord);
+                            self.guard.turn();
+                            reached_edge = false;
+                            break;
+                        }
+                        self.visited.insert(
+                            Coord2D {
+                                x: self.guard.coord.x,
+                                y,
+                            },
+                            self.guard.dir,
+                        )?;
+                    }
+                    // We reached the left edge and can walk out of the map.
+                }
+                Direction::East => {
+                    for y in self.guard.coord.y..self.y_dim {
+                        if self.obstacles.contains(&Coord2D {
+                            x: self.guard.coord.x,
+                            y,
+                        }) {
+                            let new_coord = Coord2D {
+                                x: self.guard.coord.x,
+                                y: y - 1,
+                            };
+                            self.guard.update_coord(new_coord);
+                            self.guard.turn();
+                            reached_edge = false;
+                            break;
+                        }
+                        self.visited.insert(
+                            Coord2D {
+                                x: self.guard.coord.x,
+                                y,
+                            },
+                            self.guard.dir,
+                        )?;
+                    }
+                    // We reached the right edge and can walk out of the map.
+                }
+            }
+            self.iterations += 1;
+        }
+        Ok(self.visited.clone())
+    }
+}
+
+/// Returns the places visited by the guard, without the origin of the guard. The places are
+/// sorted, which makes debugging easier.
+pub fn visited_places_without_origin(lab: &mut Lab) -> Result<Vec<Coord2D>, PathLoop> {
+    let mut visited = lab.patrol()?;
+    visited.0.remove(&lab.guard_origin);
+    let mut visited_places: Vec<Coord2D> = visited.0.into_keys().collect();
+    visited_places.sort_unstable();
+    Ok(visited_places)
+}
+
+pub fn part2_unoptimized(lab: &mut Lab, keys: &[Coord2D]) -> usize {
+    let mut loop_obstructions = 0;
+
+    // We set an obstacle for each visited path except the start point
+    // and then run the patrol algorithm. The loop condition is when
+    // the guard re-visits a tile with the same direction.
+    for (idx, key) in keys.iter().enumerate() {
+        if DEBUG_P2 {
+            println!(
+                "{}/{}: Re-patrolling with additional obstacle: {:?}",
+                idx,
+                keys.len(),
+                key
+            );
+        }
+        lab.update_additional_obstacle(*key);
+        if lab.patrol().is_err() {
+            loop_obstructions += 1;
+        }
+    }
+    loop_obstructions
+}
+
+// Parallelized with rayon. It's just insane how nice this works.
+pub fn part2_parallelized(lab: &Lab, visited: &[Coord2D]) -> usize {
+    let loop_obstructions = AtomicUsize::new(0);
+    visited.par_iter().enumerate().for_each(|(_, key)| {
+        let mut lab = lab.clone();
+        lab.update_additional_obstacle(*key);
+        if lab.patrol().is_err() {
+            loop_obstructions.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    loop_obstructions.load(Ordering::Relaxed)
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Input = Lab;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        Lab::new(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut lab = input.clone();
+        match lab.patrol() {
+            Ok(visited) => visited.visited_places().into(),
+            Err(e) => Answer::Failed(e.to_string()),
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        let mut lab = input.clone();
+        match visited_places_without_origin(&mut lab) {
+            Ok(visited_places) => part2_parallelized(&lab, &visited_places).into(),
+            Err(e) => Answer::Failed(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use aoc_common::{input_lines, Answer, ParseError, Solver};
+
+const DEBUG: bool = false;
+
+#[derive(Debug, Clone)]
+pub struct Equation {
+    pub result: u64,
+    pub numbers: Vec<u64>,
+}
+
+pub fn parse_equations(data: &[u8]) -> Result<Vec<Equation>, ParseError> {
+    let mut equations = Vec::new();
+    for line in input_lines(data)? {
+        let (result, numbers) = line.split_once(":")?;
+        let result = line.parse::<u64>(result, "a test value")?;
+        let numbers = numbers
+            .split_whitespace()
+            .map(|v| line.parse::<u64>(v, "a number"))
+            .collect::<Result<Vec<u64>, ParseError>>()?;
+        if numbers.is_empty() {
+            return Err(line.expected("", "at least one number"));
+        }
+        equations.push(Equation { result, numbers });
+    }
+    Ok(equations)
+}
+
+/// Sums up the results of all equations which can be made true with the addition and
+/// multiplication operators, and with the concatenation operator if `with_concat` is set.
+pub fn total_calibration_result(equations: &[Equation], with_concat: bool) -> u64 {
+    let mut calib_result = 0;
+    for Equation { result, numbers } in equations {
+        // Start with the first number as the initial result
+        let mut prev_results = vec![numbers[0]];
+        for number in &numbers[1..] {
+            // Pre-allocate space
+            let mut next_results = Vec::with_capacity(prev_results.len() * 3);
+            for &prev_result in &prev_results {
+                next_results.push(prev_result + number);
+                next_results.push(prev_result * number);
+                if with_concat {
+                    next_results.push(format!("{}{}", prev_result, number).parse().unwrap());
+                }
+            }
+            // Update previous results
+            prev_results = next_results;
+        }
+        prev_results.sort_unstable();
+        if prev_results.binary_search(result).is_ok() {
+            if DEBUG {
+                println!(
+                    "Result {} is in possible results {:?}",
+                    result, prev_results
+                );
+            }
+            calib_result += result;
+        }
+    }
+    calib_result
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = Vec<Equation>;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_equations(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        total_calibration_result(input, false).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        total_calibration_result(input, true).into()
+    }
+}
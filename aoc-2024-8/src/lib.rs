@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::{Answer, ParseError, Solver};
+use aoc_grid::{Grid, ICoord2D};
+
+const DEBUG: bool = false;
+
+#[derive(Debug, Clone)]
+pub struct AntennaMap {
+    pub x_dim: i64,
+    pub y_dim: i64,
+    pub antennas: HashMap<char, Vec<ICoord2D>>,
+}
+
+impl AntennaMap {
+    pub fn new(data: &[u8]) -> Result<Self, ParseError> {
+        let grid = Grid::parse_with(data, "antenna map", |_, b| {
+            (b == b'.' || b.is_ascii_alphanumeric()).then_some(b)
+        })?;
+        let mut antennas: HashMap<char, Vec<ICoord2D>> = HashMap::default();
+        for (pos, &frequency) in grid.iter() {
+            if frequency != b'.' {
+                antennas
+                    .entry(frequency as char)
+                    .or_default()
+                    .push(ICoord2D::from(pos));
+            }
+        }
+        Ok(Self {
+            x_dim: grid.rows() as i64,
+            y_dim: grid.cols() as i64,
+            antennas,
+        })
+    }
+
+    pub fn unique_antinodes_p1(&self) -> usize {
+        let (x_dim, y_dim) = (self.x_dim, self.y_dim);
+        let mut antinodes = HashSet::new();
+        // Use brute-force to calculate all unique 2D vectors. Those can be used to find the anti-nodes
+        for (char, antenna_with_type) in &self.antennas {
+            if DEBUG {
+                println!("Handling antenna type: {:?}", char);
+            }
+            // The keys are the vectors which were already handled. The value is one of the start point
+            // because a vector is not unique on the 2D grid.
+            let mut handled_vecs: HashMap<ICoord2D, ICoord2D> = HashMap::new();
+            for (i, coord) in antenna_with_type.iter().enumerate() {
+                for (j, other_coord) in antenna_with_type.iter().enumerate() {
+                    if i == j {
+                        continue;
+                    }
+                    // We calculate the vector from one point to another.
+                    let vec = *other_coord - *coord;
+                    // Also calculate the negation. We want to only handle unique vectors.
+                    let neg_vec = -vec;
+                    let vec_already_handled = |v: &ICoord2D| {
+                        handled_vecs.get(v).is_some_and(|some_start_point| {
+                            some_start_point == coord || some_start_point == other_coord
+                        })
+                    };
+                    // Vector was already handled
+                    if vec_already_handled(&vec) || vec_already_handled(&neg_vec) {
+                        continue;
+                    }
+
+                    if DEBUG {
+                        println!("Handling vector from {:?} to {:?}", coord, other_coord);
+                        println!("Vector: {:?}", vec);
+                    }
+                    handled_vecs.insert(vec, *coord);
+                    let antinode1 = *other_coord + vec;
+                    if DEBUG {
+                        println!("Detected antinode: {:?}", antinode1);
+                    }
+
+                    let valid_antinode = |antinode: &ICoord2D| {
+                        !antinode.has_negative_parts() && antinode.x < x_dim && antinode.y < y_dim
+                    };
+                    if valid_antinode(&antinode1) {
+                        antinodes.insert(antinode1);
+                    }
+                    let antinode2 = *coord - vec;
+                    if DEBUG {
+                        println!("Detected antinode: {:?}", antinode2);
+                    }
+                    if valid_antinode(&antinode2) {
+                        antinodes.insert(antinode2);
+                    }
+                }
+            }
+        }
+        antinodes.len()
+    }
+
+    pub fn unique_antinodes_p2(&self) -> usize {
+        let (x_dim, y_dim) = (self.x_dim, self.y_dim);
+        let mut antinodes = HashSet::new();
+        // Use brute-force to calculate all unique 2D vectors. Those can be used to find the anti-nodes
+        for (char, antenna_with_type) in &self.antennas {
+            if DEBUG {
+                println!("Handling antenna type: {:?}", char);
+            }
+            // The keys are the vectors which were already handled. The value is one of the start point
+            // because a vector is not unique on the 2D grid.
+            let mut handled_vecs: HashMap<ICoord2D, ICoord2D> = HashMap::new();
+            for (i, coord) in antenna_with_type.iter().enumerate() {
+                antinodes.insert(*coord);
+                for (j, other_coord) in antenna_with_type.iter().enumerate() {
+                    if i == j {
+                        continue;
+                    }
+                    // We calculate the vector from one point to another.
+                    let vec = *other_coord - *coord;
+                    // Also calculate the negation. We want to only handle unique vectors.
+                    let neg_vec = -vec;
+                    let vec_already_handled = |v: &ICoord2D| {
+                        handled_vecs.get(v).is_some_and(|some_start_point| {
+                            some_start_point == coord || some_start_point == other_coord
+                        })
+                    };
+                    // Vector was already handled
+                    if vec_already_handled(&vec) || vec_already_handled(&neg_vec) {
+                        continue;
+                    }
+
+                    if DEBUG {
+                        println!("Handling vector from {:?} to {:?}", coord, other_coord);
+                        println!("Vector: {:?}", vec);
+                    }
+                    handled_vecs.insert(vec, *coord);
+                    let mut antinode = *other_coord + vec;
+                    loop {
+                        if antinode.has_negative_parts()
+                            || antinode.x >= x_dim
+                            || antinode.y >= y_dim
+                        {
+                            break;
+                        }
+                        if antinodes.insert(antinode) && DEBUG {
+                            println!("Detected antinode: {:?}", antinode);
+                        }
+                        antinode += vec;
+                    }
+                    antinode = *coord - vec;
+                    loop {
+                        if antinode.has_negative_parts()
+                            || antinode.x >= x_dim
+                            || antinode.y >= y_dim
+                        {
+                            break;
+                        }
+                        if antinodes.insert(antinode) && DEBUG {
+                            println!("Detected antinode: {:?}", antinode);
+                        }
+                        antinode -= vec;
+                    }
+                }
+            }
+        }
+        antinodes.len()
+    }
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input = AntennaMap;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        AntennaMap::new(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.unique_antinodes_p1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.unique_antinodes_p2().into()
+    }
+}
@@ -0,0 +1,264 @@
+use aoc_common::{input_text, Answer, Line, ParseError, Solver};
+
+const DEBUG: bool = false;
+
+pub struct Compactor {
+    pub files_list: Vec<u8>,
+    pub free_spaces_list: Vec<u8>,
+    pub total_num_files: usize,
+    pub total_num_free_spaces: usize,
+}
+
+impl Compactor {
+    /// Parses the disk map from the raw puzzle input, ignoring trailing whitespace.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        Self::new(input_text(data)?.trim_end())
+    }
+
+    pub fn new(disk_map: &str) -> Result<Self, ParseError> {
+        let line = Line::new(1, disk_map);
+        let mut files_list = Vec::new();
+        let mut free_spaces_list = Vec::new();
+        let mut total_num_files = 0;
+        let mut total_num_free_spaces = 0;
+        for (idx, num) in disk_map.char_indices() {
+            let next_val = match num.to_digit(10) {
+                Some(digit) => digit as u8,
+                None => return Err(line.unexpected(idx, "disk map")),
+            };
+            if files_list.len() == free_spaces_list.len() {
+                files_list.push(next_val);
+                total_num_files += next_val as usize;
+            } else {
+                free_spaces_list.push(next_val);
+                total_num_free_spaces += next_val as usize;
+            }
+        }
+        if files_list.is_empty() {
+            return Err(ParseError::missing("files", "disk map"));
+        }
+        Ok(Self {
+            files_list,
+            free_spaces_list,
+            total_num_files,
+            total_num_free_spaces,
+        })
+    }
+
+    pub fn create_uncompacted(&self) -> Vec<Option<usize>> {
+        let mut uncompacted: Vec<Option<usize>> =
+            Vec::with_capacity(self.total_num_free_spaces + self.total_num_files);
+        let mut file_idx = 0;
+        let mut free_spaces_idx = 0;
+        loop {
+            if DEBUG {
+                println!("compacted len: {}", uncompacted.len());
+            }
+            if uncompacted.len() == uncompacted.capacity() {
+                break;
+            }
+            if file_idx < self.files_list.len() {
+                for _ in 0..self.files_list[file_idx] {
+                    uncompacted.push(Some(file_idx));
+                }
+                file_idx += 1;
+            }
+            if free_spaces_idx < self.free_spaces_list.len() {
+                for _ in 0..self.free_spaces_list[free_spaces_idx] {
+                    uncompacted.push(None);
+                }
+                free_spaces_idx += 1;
+            }
+        }
+        uncompacted
+    }
+
+    pub fn run_compacting_simple_p1(&self) -> (usize, Vec<usize>) {
+        let mut compacted = self.create_uncompacted();
+        if DEBUG {
+            println!("uncompacted: {:?}", compacted);
+        }
+        // Now loop from right to left and left to right at the same time until the indexes are
+        // equal and move numbers on the right to the free spaces.
+        let mut left_idx = 0;
+        let mut right_idx = compacted.len() - 1;
+        loop {
+            while left_idx < right_idx && compacted[left_idx].is_some() {
+                left_idx += 1;
+            }
+            while compacted[right_idx].is_none() {
+                right_idx -= 1;
+            }
+            if left_idx >= right_idx {
+                break;
+            }
+            compacted.swap(left_idx, right_idx);
+        }
+        if DEBUG {
+            println!("compacted: {:?}", compacted);
+        }
+        let mut checksum = 0;
+        let mut compacted_ids = Vec::new();
+        for (idx, val) in compacted.iter().enumerate() {
+            if val.is_none() {
+                break;
+            }
+            checksum += val.unwrap() * idx;
+            compacted_ids.push(val.unwrap());
+        }
+        (checksum, compacted_ids)
+    }
+
+    pub fn run_compacting_p2(&self) -> (usize, Vec<Option<usize>>) {
+        let mut compacted = self.create_uncompacted();
+        let mut right_idx = compacted.len() - 1;
+        let mut left_idx;
+        let mut files_chunk_len;
+        let mut free_spaces_len;
+        let mut dest_start;
+        while right_idx > 0 {
+            if compacted[right_idx].is_none() {
+                right_idx -= 1;
+                continue;
+            }
+            let block_id = compacted[right_idx].unwrap();
+            // Calculate the block size
+            files_chunk_len = 1;
+            while right_idx > 0
+                && compacted[right_idx - 1].is_some()
+                && compacted[right_idx - 1].unwrap() == block_id
+            {
+                files_chunk_len += 1;
+                right_idx -= 1;
+            }
+            if right_idx == 0 {
+                break;
+            }
+            if DEBUG {
+                println!(
+                    "Detected files block with ID {:?} at position {} with length {}",
+                    compacted[right_idx], right_idx, files_chunk_len
+                );
+            }
+            let mut moved_block = false;
+            left_idx = 0;
+            // Now we check all free spaces to see if one can accomodate the block and move it
+            // where appropriate.
+            while left_idx < right_idx {
+                while compacted[left_idx].is_some() && left_idx < right_idx {
+                    left_idx += 1;
+                }
+                dest_start = left_idx;
+                free_spaces_len = 0;
+                while compacted[left_idx].is_none() {
+                    left_idx += 1;
+                    free_spaces_len += 1;
+                    if free_spaces_len == files_chunk_len {
+                        // copy block to free spaces
+                        compacted.copy_within(right_idx..right_idx + files_chunk_len, dest_start);
+                        if DEBUG {
+                            println!(
+                                "Moving block {:?} to destination index {}",
+                                &compacted[right_idx..right_idx + files_chunk_len],
+                                dest_start
+                            );
+                        }
+                        compacted[right_idx..right_idx + files_chunk_len].fill(None);
+                        // Search for next slock to move.
+                        moved_block = true;
+                        break;
+                    }
+                }
+                // Search for next block to move.
+                if moved_block {
+                    break;
+                }
+                // In any other case, the free spaces block was not large enough, so we search for
+                // the next one by simply re-running the loop.
+                left_idx += 1;
+            }
+            // prepare next loop iteration
+            right_idx -= 1;
+        }
+        if DEBUG {
+            println!("compacted part 2: {:?}", compacted);
+        }
+        let mut checksum = 0;
+        for (idx, opt_val) in compacted.iter().enumerate() {
+            if let Some(val) = opt_val {
+                checksum += idx * val;
+            }
+        }
+        (checksum, compacted)
+    }
+
+    /// Compacts the files block by block without creating the uncompacted disk first. The files
+    /// are taken from the front in order, and each free spaces block after a file is filled with
+    /// the blocks of the last file which was not moved yet.
+    pub fn run_compacting_fancy(&self) -> (usize, Vec<usize>) {
+        let mut ids = Vec::with_capacity(self.total_num_files);
+        let mut last_file_idx = self.files_list.len() - 1;
+        let mut last_file_remaining = self.files_list[last_file_idx];
+
+        if DEBUG {
+            println!("file list: {:?}", self.files_list);
+            println!("free spaces: {:?}", self.free_spaces_list);
+        }
+        'compacting: for file_idx in 0..self.files_list.len() {
+            if ids.len() == self.total_num_files {
+                break;
+            }
+            // Handle regular files first. Those are simply pushed onto the compacted list. Only
+            // the blocks which were not moved yet remain of the last file.
+            let file_len = if file_idx == last_file_idx {
+                last_file_remaining
+            } else {
+                self.files_list[file_idx]
+            };
+            for _ in 0..file_len {
+                ids.push(file_idx);
+            }
+            let free_spaces = self.free_spaces_list.get(file_idx).copied().unwrap_or(0);
+            if DEBUG {
+                println!("next available free spaces: {}", free_spaces);
+            }
+            for _ in 0..free_spaces {
+                if ids.len() == self.total_num_files {
+                    break 'compacting;
+                }
+                while last_file_remaining == 0 {
+                    last_file_idx -= 1;
+                    last_file_remaining = self.files_list[last_file_idx];
+                }
+                if DEBUG {
+                    println!("moved block with ID {} to compacted", last_file_idx);
+                }
+                ids.push(last_file_idx);
+                last_file_remaining -= 1;
+            }
+        }
+        let mut checksum = 0;
+        for (idx, val) in ids.iter().enumerate() {
+            checksum += idx * val;
+        }
+        (checksum, ids)
+    }
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Input = Compactor;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        Compactor::parse(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.run_compacting_simple_p1().0.into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.run_compacting_p2().0.into()
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    panic::AssertUnwindSafe,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{ParseError, PuzzleInput};
+
+use crate::alloc::AllocStats;
+
+/// One implementation of a computation which is compared against the other variants of its group.
+pub struct Variant<I> {
+    pub name: &'static str,
+    pub run: fn(&I) -> u64,
+    /// The variant is too slow for the real input and only runs on the examples.
+    pub examples_only: bool,
+}
+
+impl<I> Variant<I> {
+    pub fn new(name: &'static str, run: fn(&I) -> u64) -> Self {
+        Self {
+            name,
+            run,
+            examples_only: false,
+        }
+    }
+
+    pub fn examples_only(name: &'static str, run: fn(&I) -> u64) -> Self {
+        Self {
+            name,
+            run,
+            examples_only: true,
+        }
+    }
+}
+
+/// Variants of one computation of a day. All variants work on the same parsed input and must
+/// return the same answer.
+pub struct Group<I> {
+    pub day: u8,
+    pub name: &'static str,
+    pub parse: fn(&[u8]) -> Result<I, ParseError>,
+    pub variants: Vec<Variant<I>>,
+}
+
+/// Result of one variant for one input.
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub variant: &'static str,
+    /// Answer of the variant, or [None] if it panicked.
+    pub answer: Option<u64>,
+    /// Fastest of all runs.
+    pub time: Duration,
+    /// Allocations of the first run.
+    pub allocs: AllocStats,
+}
+
+/// Type-erased interface of [Group], so groups with different inputs can be listed together.
+pub trait Benchmark {
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+
+    /// Runs all variants on the input, each of them `runs` times. Variants which only run on the
+    /// examples are skipped for the real input.
+    fn run(&self, input: &PuzzleInput, runs: usize) -> Result<Vec<Measurement>, ParseError>;
+}
+
+impl<I> Benchmark for Group<I> {
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn run(&self, input: &PuzzleInput, runs: usize) -> Result<Vec<Measurement>, ParseError> {
+        let parsed = (self.parse)(&input.data)?;
+        Ok(self
+            .variants
+            .iter()
+            .filter(|variant| !(variant.examples_only && input.is_default()))
+            .map(|variant| measure(variant, &parsed, runs))
+            .collect())
+    }
+}
+
+fn measure<I>(variant: &Variant<I>, input: &I, runs: usize) -> Measurement {
+    let mut measurement = Measurement {
+        variant: variant.name,
+        answer: None,
+        time: Duration::MAX,
+        allocs: AllocStats::default(),
+    };
+    for run in 0..runs.max(1) {
+        let allocs = AllocStats::now();
+        let start = Instant::now();
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| (variant.run)(input)));
+        let time = start.elapsed();
+        let Ok(answer) = result else {
+            measurement.answer = None;
+            break;
+        };
+        if run == 0 {
+            measurement.allocs = allocs.since();
+        }
+        measurement.answer = Some(answer);
+        measurement.time = measurement.time.min(time);
+    }
+    measurement
+}
+
+/// Whether all variants returned the same answer without panicking.
+pub fn variants_agree(measurements: &[Measurement]) -> bool {
+    let first = measurements.first().and_then(|m| m.answer);
+    measurements
+        .iter()
+        .all(|m| m.answer.is_some() && m.answer == first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_numbers(data: &[u8]) -> Result<Vec<u64>, ParseError> {
+        Ok(data.iter().map(|&b| (b - b'0') as u64).collect())
+    }
+
+    #[test]
+    fn disagreeing_variants() {
+        let group = Group {
+            day: 1,
+            name: "sum",
+            parse: parse_numbers,
+            variants: vec![
+                Variant::new("iter", |numbers: &Vec<u64>| numbers.iter().sum()),
+                Variant::new("loop", |numbers| {
+                    let mut sum = 0;
+                    for n in numbers {
+                        sum += n;
+                    }
+                    sum
+                }),
+                Variant::examples_only("off_by_one", |numbers| numbers.iter().sum::<u64>() + 1),
+            ],
+        };
+        let mut input = PuzzleInput {
+            data: b"123".to_vec(),
+            ..Default::default()
+        };
+        input.meta.name = "example".to_string();
+        let measurements = group.run(&input, 2).unwrap();
+        assert_eq!(measurements.len(), 3);
+        assert_eq!(measurements[0].answer, Some(6));
+        assert!(!variants_agree(&measurements));
+
+        // The broken variant is skipped for the real input.
+        input.meta.name = aoc_common::DEFAULT_INPUT_NAME.to_string();
+        input.meta.registered = true;
+        let measurements = group.run(&input, 1).unwrap();
+        assert_eq!(measurements.len(), 2);
+        assert!(variants_agree(&measurements));
+    }
+}
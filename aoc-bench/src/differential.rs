@@ -0,0 +1,115 @@
+//! Differential tests which run all variants of a benchmark group on random inputs and check that
+//! they agree. The inputs are generated as structured values and rendered to the puzzle format, so
+//! proptest can shrink a counterexample to a minimal input. The seeds of failing cases are saved in
+//! `proptest-regressions/differential.txt` and replayed before any new cases are generated, so the
+//! file should be committed together with the fix. The seeds only replay with the same strategies,
+//! so each shrunk counterexample is also kept as a plain test at the end of this module.
+use aoc_common::PuzzleInput;
+use proptest::prelude::*;
+
+use crate::{bench::variants_agree, groups};
+
+/// Runs all variants of the group on the rendered input. The input is not named like the real
+/// input, so the variants which only run on the examples are included.
+fn check_agreement(day: u8, name: &str, data: String) -> Result<(), TestCaseError> {
+    let group = groups::all()
+        .into_iter()
+        .find(|group| group.day() == day && group.name() == name)
+        .unwrap_or_else(|| panic!("no group {} for day {}", name, day));
+    let mut input = PuzzleInput {
+        data: data.into_bytes(),
+        ..Default::default()
+    };
+    input.meta.name = "generated".to_string();
+    let measurements = group
+        .run(&input, 1)
+        .map_err(|e| TestCaseError::fail(e.to_string()))?;
+    let answers: Vec<_> = measurements.iter().map(|m| (m.variant, m.answer)).collect();
+    prop_assert!(variants_agree(&measurements), "answers: {:?}", answers);
+    Ok(())
+}
+
+fn render_lines<T>(items: &[T], render: impl Fn(&T) -> String) -> String {
+    items.iter().map(|item| render(item) + "\n").collect()
+}
+
+fn join_values<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(T::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Disk map with alternating files and free spaces, starting and ending with a file.
+fn render_disk_map(blocks: &[(u8, u8)]) -> String {
+    let mut disk_map: String = blocks
+        .iter()
+        .flat_map(|&(file, free_spaces)| [file, free_spaces])
+        .map(|len| char::from(b'0' + len))
+        .collect();
+    disk_map.pop();
+    disk_map
+}
+
+fn render_onsen(patterns: &[String], designs: &[String]) -> String {
+    format!(
+        "{}\n\n{}",
+        patterns.join(", "),
+        render_lines(designs, String::clone)
+    )
+}
+
+fn stripes(max_len: usize) -> impl Strategy<Value = String> {
+    proptest::string::string_regex(&format!("[wubrg]{{1,{}}}", max_len)).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn location_lists(pairs in prop::collection::vec((1u32..50, 1u32..50), 1..20)) {
+        let data = render_lines(&pairs, |(left, right)| format!("{}   {}", left, right));
+        check_agreement(1, "similarity score", data)?;
+    }
+
+    #[test]
+    fn level_rows(rows in prop::collection::vec(prop::collection::vec(1i32..12, 2..8), 1..10)) {
+        check_agreement(2, "safe reports", render_lines(&rows, |row| join_values(row)))?;
+    }
+
+    #[test]
+    fn disk_maps(blocks in prop::collection::vec((1u8..=9, 0u8..=9), 1..20)) {
+        check_agreement(9, "block compacting", render_disk_map(&blocks))?;
+    }
+
+    #[test]
+    fn towel_patterns(
+        patterns in prop::collection::vec(stripes(4), 1..8),
+        designs in prop::collection::vec(stripes(12), 1..5),
+    ) {
+        let data = render_onsen(&patterns, &designs);
+        check_agreement(19, "possible designs", data.clone())?;
+        check_agreement(19, "arrangements", data)?;
+    }
+}
+
+proptest! {
+    // Every case blinks the stones 25 times with each variant.
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn stone_lists(stones in prop::collection::vec(0u64..1_000_000, 1..4)) {
+        check_agreement(11, "stones after 25 blinks", join_values(&stones))?;
+    }
+}
+
+/// The counterexample `disk_maps` once shrank to: a single file of one block and no free space.
+#[test]
+fn single_block_disk_map() {
+    let disk_map = render_disk_map(&[(1, 0)]);
+    assert_eq!(disk_map, "1");
+    check_agreement(9, "block compacting", disk_map).unwrap();
+    let compactor = aoc_2024_9::Compactor::new("1").unwrap();
+    assert_eq!(compactor.run_compacting_simple_p1(), (0, vec![0]));
+    assert_eq!(compactor.run_compacting_fancy(), (0, vec![0]));
+    assert_eq!(compactor.run_compacting_p2(), (0, vec![Some(0)]));
+}
@@ -0,0 +1,273 @@
+use std::collections::HashSet;
+
+use aoc_2024_11::{
+    count_map::StoneCounts, list_based::StonesInRamListBased, StoneRecursor, CALC_POINT_P1,
+    CALC_POINT_P2,
+};
+use aoc_2024_17::{compile::CompiledProgram, Computer};
+use aoc_2024_19::Onsen;
+use aoc_2024_24::{netlist::Netlist, Monitoring};
+use aoc_common::ParseError;
+
+use crate::bench::{Benchmark, Group, Variant};
+
+fn count_safe_reports(reports: &[Vec<i32>], unsafe_line: fn(&[i32], bool) -> bool) -> u64 {
+    reports
+        .iter()
+        .filter(|values| !unsafe_line(values, values[1] > values[0]))
+        .count() as u64
+}
+
+fn total_stones(stones: &[u64], additional_stones: impl Fn(u64) -> usize) -> u64 {
+    (stones.len()
+        + stones
+            .iter()
+            .map(|&val| additional_stones(val))
+            .sum::<usize>()) as u64
+}
+
+/// Candidates for register A which are tried by the day 17 brute-force search.
+const QUINE_CANDIDATES: u64 = 1 << 18;
+
+/// Lowest candidate for register A which makes the program output itself, or
+/// [QUINE_CANDIDATES] if there is none.
+fn first_quine(mut outputs_itself: impl FnMut(u64) -> bool) -> u64 {
+    (0..QUINE_CANDIDATES)
+        .find(|&a| outputs_itself(a))
+        .unwrap_or(QUINE_CANDIDATES)
+}
+
+/// Pairs of numbers which are added by the day 24 circuit.
+const ADDER_PAIRS: u64 = 1 << 16;
+
+/// Pseudo-random pairs of 45-bit numbers, which is the width of the day 24 adder.
+fn adder_pairs() -> impl Iterator<Item = (u64, u64)> {
+    (0..ADDER_PAIRS).map(|idx| {
+        (
+            idx.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 19,
+            idx.wrapping_mul(0xc2b2_ae3d_27d4_eb4f) >> 19,
+        )
+    })
+}
+
+/// Sum of the numbers on the `z` wires for all [adder_pairs].
+fn adder_checksum(monitoring: &Monitoring, evaluate: fn(&Netlist, &[(u64, u64)]) -> u64) -> u64 {
+    let netlist = Netlist::new(&monitoring.circuit).unwrap();
+    let pairs: Vec<_> = adder_pairs().collect();
+    pairs
+        .chunks(64)
+        .map(|batch| evaluate(&netlist, batch))
+        .fold(0, u64::wrapping_add)
+}
+
+fn possible_designs(onsen: &Onsen, possible: impl Fn(usize, &str) -> bool) -> u64 {
+    onsen
+        .towels
+        .iter()
+        .filter(|towel| possible(onsen.largest_pattern, towel))
+        .count() as u64
+}
+
+fn arrangements_with(onsen: &Onsen, count: fn(usize, &HashSet<String>, &str) -> usize) -> u64 {
+    onsen
+        .towels
+        .iter()
+        .map(|towel| count(onsen.largest_pattern, &onsen.patterns, towel) as u64)
+        .sum()
+}
+
+/// All groups of variants which coexist in the solutions.
+pub fn all() -> Vec<Box<dyn Benchmark>> {
+    vec![
+        Box::new(Group {
+            day: 1,
+            name: "similarity score",
+            parse: aoc_2024_1::parse_location_lists,
+            variants: vec![
+                Variant::new("part2_dumb", |(left, right)| {
+                    aoc_2024_1::part2_dumb(left.clone(), right.clone()) as u64
+                }),
+                Variant::new("part2_smart", |(left, right)| {
+                    aoc_2024_1::part2_smart(left.clone(), right.clone()) as u64
+                }),
+            ],
+        }),
+        Box::new(Group {
+            day: 2,
+            name: "safe reports",
+            parse: aoc_2024_2::parse_reports,
+            variants: vec![
+                Variant::new("unsafe_line_dumb", |reports| {
+                    count_safe_reports(reports, aoc_2024_2::unsafe_line_dumb)
+                }),
+                Variant::new("unsafe_line_smart", |reports| {
+                    count_safe_reports(reports, aoc_2024_2::unsafe_line_smart)
+                }),
+            ],
+        }),
+        Box::new(Group {
+            day: 6,
+            name: "loop obstructions",
+            parse: |data| {
+                let mut lab = aoc_2024_6::Lab::new(data)?;
+                let visited = aoc_2024_6::visited_places_without_origin(&mut lab)
+                    .map_err(|e| ParseError::input(e.to_string()))?;
+                Ok((lab, visited))
+            },
+            variants: vec![
+                Variant::new("part2_unoptimized", |(lab, visited)| {
+                    aoc_2024_6::part2_unoptimized(&mut lab.clone(), visited) as u64
+                }),
+                Variant::new("part2_parallelized", |(lab, visited)| {
+                    aoc_2024_6::part2_parallelized(lab, visited) as u64
+                }),
+            ],
+        }),
+        Box::new(Group {
+            day: 9,
+            name: "block compacting",
+            parse: aoc_2024_9::Compactor::parse,
+            variants: vec![
+                Variant::new("run_compacting_simple_p1", |compactor| {
+                    compactor.run_compacting_simple_p1().0 as u64
+                }),
+                Variant::new("run_compacting_fancy", |compactor| {
+                    compactor.run_compacting_fancy().0 as u64
+                }),
+            ],
+        }),
+        Box::new(Group {
+            day: 11,
+            name: "stones after 25 blinks",
+            parse: aoc_2024_11::get_initial_stones,
+            variants: vec![
+                Variant::new("blink_recursion", |stones| {
+                    let recursor = StoneRecursor::new_from_stones(stones.clone());
+                    total_stones(stones, |val| {
+                        let mut num_of_stones = 0;
+                        recursor.blink_recursion(val, 0, CALC_POINT_P1, &mut num_of_stones);
+                        num_of_stones
+                    })
+                }),
+                Variant::new("blink_with_stack", |stones| {
+                    total_stones(stones, |val| {
+                        StoneRecursor::blink_with_stack(val, CALC_POINT_P1)
+                    })
+                }),
+                Variant::new("StonesInRamListBased", |stones| {
+                    let mut list = StonesInRamListBased(stones.clone());
+                    for _ in 0..CALC_POINT_P1 {
+                        list.blink();
+                    }
+                    list.num_of_stones() as u64
+                }),
+                Variant::new("StoneCounts", |stones| {
+                    let mut counts = StoneCounts::<u64>::new(stones).unwrap();
+                    counts.blink_n_times(CALC_POINT_P1).unwrap();
+                    counts.total().unwrap()
+                }),
+            ],
+        }),
+        Box::new(Group {
+            day: 11,
+            name: "stones after 75 blinks",
+            parse: aoc_2024_11::get_initial_stones,
+            variants: vec![
+                Variant::new("blink_n_times_parallelized", |stones| {
+                    StoneRecursor::new_from_stones(stones.clone())
+                        .blink_n_times_parallelized(CALC_POINT_P2) as u64
+                }),
+                Variant::new("StoneCounts", |stones| {
+                    let mut counts = StoneCounts::<u64>::new(stones).unwrap();
+                    counts.blink_n_times(CALC_POINT_P2).unwrap();
+                    counts.total().unwrap()
+                }),
+            ],
+        }),
+        Box::new(Group {
+            day: 17,
+            name: "quine brute force",
+            parse: Computer::new_from_data,
+            variants: vec![
+                Variant::new("execute_p2_brute_force", |(computer, memory)| {
+                    let mut computer = *computer;
+                    first_quine(|a| computer.execute_p2_brute_force(a, memory))
+                }),
+                Variant::new("CompiledProgram", |(_, memory)| {
+                    let compiled = CompiledProgram::new(memory);
+                    first_quine(|a| {
+                        compiled.outputs_itself(Computer::new(a, 0, 0), usize::MAX) == Some(true)
+                    })
+                }),
+            ],
+        }),
+        Box::new(Group {
+            day: 19,
+            name: "possible designs",
+            parse: aoc_2024_19::parse_onsen,
+            variants: vec![
+                Variant::new("towel_matcher_with_stack", |onsen| {
+                    possible_designs(onsen, |largest, towel| {
+                        aoc_2024_19::towel_matcher_with_stack(largest, &onsen.patterns, towel)
+                    })
+                }),
+                Variant::new("towel_matcher_exhaustive_dynamic_programming", |onsen| {
+                    possible_designs(onsen, |largest, towel| {
+                        aoc_2024_19::towel_matcher_exhaustive_dynamic_programming(
+                            largest,
+                            &onsen.patterns,
+                            towel,
+                        ) > 0
+                    })
+                }),
+            ],
+        }),
+        // The matchers which keep track of the matched patterns explode on the real input.
+        Box::new(Group {
+            day: 19,
+            name: "arrangements",
+            parse: aoc_2024_19::parse_onsen,
+            variants: vec![
+                Variant::new("towel_matcher_exhaustive_dynamic_programming", |onsen| {
+                    arrangements_with(
+                        onsen,
+                        aoc_2024_19::towel_matcher_exhaustive_dynamic_programming,
+                    )
+                }),
+                Variant::examples_only("towel_matcher_counting", |onsen| {
+                    arrangements_with(onsen, aoc_2024_19::towel_matcher_counting)
+                }),
+                Variant::examples_only("towel_matching_using_set_p2", |onsen| {
+                    arrangements_with(onsen, aoc_2024_19::towel_matching_using_set_p2)
+                }),
+                Variant::examples_only("towel_matcher_exhaustive_dfs_recursive", |onsen| {
+                    arrangements_with(onsen, aoc_2024_19::towel_matcher_exhaustive_dfs_recursive)
+                }),
+            ],
+        }),
+        Box::new(Group {
+            day: 24,
+            name: "adder sweep",
+            parse: Monitoring::new,
+            variants: vec![
+                Variant::new("Netlist::evaluate", |monitoring| {
+                    adder_checksum(monitoring, |netlist, batch| {
+                        batch
+                            .iter()
+                            .map(|&(x, y)| netlist.evaluate(x, y).unwrap())
+                            .fold(0, u64::wrapping_add)
+                    })
+                }),
+                Variant::new("Netlist::evaluate_batch", |monitoring| {
+                    adder_checksum(monitoring, |netlist, batch| {
+                        netlist
+                            .evaluate_batch(batch)
+                            .unwrap()
+                            .into_iter()
+                            .fold(0, u64::wrapping_add)
+                    })
+                }),
+            ],
+        }),
+    ]
+}
@@ -0,0 +1,112 @@
+//! Benchmarks the alternative implementations which coexist in some days against each other.
+//! Every variant runs on the examples and the real input of its day, and the benchmark fails if
+//! the variants of a group do not agree on the answer.
+use aoc_common::{day_dir, format_duration, InputSource, DEFAULT_INPUT_NAME};
+use clap::Parser;
+
+mod alloc;
+mod bench;
+mod groups;
+
+#[cfg(test)]
+mod differential;
+
+use alloc::CountingAllocator;
+use bench::{variants_agree, Measurement};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Parser, Debug)]
+#[command(about = "Compare the alternative implementations of the Advent of Code 2024 solutions")]
+struct Cli {
+    /// Only benchmark this day.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Number of runs of each variant. The fastest run is reported.
+    #[arg(short, long, default_value_t = 3)]
+    runs: usize,
+    /// Skip the real inputs and only run the examples.
+    #[arg(long)]
+    examples_only: bool,
+}
+
+/// Names of all inputs of a day, with the examples first and the real input last.
+fn input_names(day: u8) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(day_dir(day))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_by_key(|name| (name == DEFAULT_INPUT_NAME, name.clone()));
+    names
+}
+
+/// Prints the measurement of a variant, with its speedup over the first variant of the group.
+fn print_measurement(measurement: &Measurement, baseline: &Measurement) {
+    let (answer, speedup) = match (measurement.answer, baseline.answer) {
+        (Some(answer), Some(_)) => (
+            answer.to_string(),
+            baseline.time.as_secs_f64() / measurement.time.as_secs_f64(),
+        ),
+        (Some(answer), None) => (answer.to_string(), f64::NAN),
+        (None, _) => ("panicked".to_string(), f64::NAN),
+    };
+    println!(
+        "    {:<46} {:>10} {:>7.1}x {:>10} allocs {:>12} bytes  {}",
+        measurement.variant,
+        format_duration(measurement.time),
+        speedup,
+        measurement.allocs.allocations,
+        measurement.allocs.bytes,
+        answer
+    );
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let mut failures = Vec::new();
+    for group in groups::all() {
+        if cli.day.is_some_and(|day| day != group.day()) {
+            continue;
+        }
+        for input_name in input_names(group.day()) {
+            if cli.examples_only && input_name == DEFAULT_INPUT_NAME {
+                continue;
+            }
+            let title = format!("day {} {} ({})", group.day(), group.name(), input_name);
+            let input = match InputSource::Named(input_name).load(day_dir(group.day())) {
+                Ok(input) => input,
+                Err(e) => {
+                    failures.push(format!("{}: {}", title, e));
+                    continue;
+                }
+            };
+            println!("{}", title);
+            let measurements = match group.run(&input, cli.runs) {
+                Ok(measurements) => measurements,
+                Err(e) => {
+                    failures.push(format!("{}: {}", title, e.report(&input.origin)));
+                    continue;
+                }
+            };
+            for measurement in &measurements {
+                print_measurement(measurement, &measurements[0]);
+            }
+            if !variants_agree(&measurements) {
+                failures.push(format!("{}: variants disagree", title));
+            }
+        }
+    }
+    if !failures.is_empty() {
+        println!();
+        for failure in &failures {
+            println!("FAIL {}", failure);
+        }
+        std::process::exit(1);
+    }
+}
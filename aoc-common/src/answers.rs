@@ -0,0 +1,160 @@
+use std::{collections::HashMap, fmt::Display, sync::OnceLock};
+
+use crate::{Answer, InputMeta, Part};
+
+/// Known answers which are verified by the `verify` command of the runner.
+pub const ANSWERS: &str = include_str!("../../answers.txt");
+
+/// Result of comparing an answer against the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// There is no known answer for the day, part and input.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Registry of known answers, keyed by day, part and input name.
+///
+/// Each line of the answers file contains the day, the part, the input name and the answer,
+/// separated by whitespace. Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default, Clone)]
+pub struct Answers {
+    answers: HashMap<(u8, Part, String), String>,
+}
+
+impl Answers {
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let mut answers = HashMap::new();
+        for (idx, line) in data.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 4 {
+                return Err(format!(
+                    "line {}: expected day, part, input and answer",
+                    idx + 1
+                ));
+            }
+            let day = fields[0]
+                .parse::<u8>()
+                .map_err(|_| format!("line {}: invalid day {}", idx + 1, fields[0]))?;
+            let part = fields[1]
+                .parse::<u8>()
+                .ok()
+                .and_then(|part| Part::try_from(part).ok())
+                .ok_or_else(|| format!("line {}: invalid part {}", idx + 1, fields[1]))?;
+            answers.insert((day, part, fields[2].to_string()), fields[3].to_string());
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u8, part: Part, input: &str) -> Option<&str> {
+        self.answers
+            .get(&(day, part, input.to_string()))
+            .map(|answer| answer.as_str())
+    }
+
+    /// Names of all inputs of the given day which have at least one known answer, sorted by name.
+    pub fn inputs(&self, day: u8) -> Vec<&str> {
+        let mut inputs: Vec<&str> = self
+            .answers
+            .keys()
+            .filter(|(answer_day, _, _)| *answer_day == day)
+            .map(|(_, _, input)| input.as_str())
+            .collect();
+        inputs.sort();
+        inputs.dedup();
+        inputs
+    }
+
+    pub fn check(&self, day: u8, part: Part, input: &str, answer: &Answer) -> Verdict {
+        match self.get(day, part, input) {
+            Some(expected) if *expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+/// Answers registry which is compiled into the binaries.
+pub fn known_answers() -> &'static Answers {
+    static ANSWERS_REGISTRY: OnceLock<Answers> = OnceLock::new();
+    ANSWERS_REGISTRY.get_or_init(|| Answers::parse(ANSWERS).expect("invalid answers file"))
+}
+
+/// Checks an answer of a day binary against the registry and reports mismatches. Unlike an
+/// assertion, this does not abort the run. Inputs from outside the directory of the day are never
+/// checked, even if they are named like a registered input.
+pub fn check_answer(day: u8, part: Part, meta: &InputMeta, answer: impl Into<Answer>) -> Verdict {
+    let answer = answer.into();
+    if !meta.registered {
+        return Verdict::Unknown;
+    }
+    let verdict = known_answers().check(day, part, &meta.name, &answer);
+    if let Verdict::Fail { expected } = &verdict {
+        eprintln!(
+            "Day {} part {} ({}): answer {} does not match the known answer {}",
+            day, part, meta.name, answer, expected
+        );
+    }
+    verdict
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_check() {
+        let answers = Answers::parse("# comment\n7 1 example 3749\n17 1 input 3,5,0\n").unwrap();
+        assert_eq!(answers.get(7, Part::One, "example"), Some("3749"));
+        assert_eq!(
+            answers.check(7, Part::One, "example", &Answer::Number(3749)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(7, Part::One, "example", &Answer::Number(3748)),
+            Verdict::Fail {
+                expected: "3749".to_string()
+            }
+        );
+        assert_eq!(
+            answers.check(17, Part::One, "input", &Answer::from("3,5,0")),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(7, Part::Two, "example", &Answer::Number(1)),
+            Verdict::Unknown
+        );
+        assert_eq!(answers.inputs(7), vec!["example"]);
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert!(Answers::parse("7 1 example").is_err());
+        assert!(Answers::parse("7 3 example 1").is_err());
+        assert!(Answers::parse("x 1 example 1").is_err());
+    }
+
+    #[test]
+    fn registry_is_valid() {
+        assert!(Answers::parse(ANSWERS).is_ok());
+    }
+}
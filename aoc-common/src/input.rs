@@ -0,0 +1,342 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::ParseError;
+
+/// Environment variable which selects the puzzle input if none is passed on the command line.
+pub const INPUT_ENV_VAR: &str = "AOC_INPUT";
+
+/// Name of the input which is used if no input was selected explicitly.
+pub const DEFAULT_INPUT_NAME: &str = "input";
+
+/// File extension of the optional metadata file which is placed next to an input file.
+pub const META_EXTENSION: &str = "meta";
+
+/// Source of the puzzle input.
+///
+/// The string representation is parsed as follows:
+///
+///  - `-` reads the input from stdin.
+///  - Anything containing a path separator or a file extension is treated as a path.
+///  - Everything else is the name of an input inside the directory of the day, for example
+///    `example0` for `example0.txt` or `input` for `input.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+    Named(String),
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        InputSource::Named(DEFAULT_INPUT_NAME.to_string())
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("empty input name".to_string());
+        }
+        if s == "-" {
+            return Ok(InputSource::Stdin);
+        }
+        if s.contains(std::path::MAIN_SEPARATOR) || s.contains('/') || s.contains('.') {
+            return Ok(InputSource::Path(PathBuf::from(s)));
+        }
+        Ok(InputSource::Named(s.to_string()))
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "-"),
+            InputSource::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl InputSource {
+    /// Input source selected with the [INPUT_ENV_VAR] environment variable, if it is set.
+    pub fn from_env() -> Option<Result<Self, String>> {
+        std::env::var(INPUT_ENV_VAR)
+            .ok()
+            .map(|value| value.parse::<InputSource>())
+    }
+
+    /// Selects the input source. An explicitly passed source takes precedence over the
+    /// environment variable, and the default input is used if neither is set.
+    pub fn select(explicit: Option<InputSource>) -> Result<Self, String> {
+        match explicit {
+            Some(source) => Ok(source),
+            None => Self::from_env().unwrap_or_else(|| Ok(Self::default())),
+        }
+    }
+
+    /// Reads the input and its metadata. Named inputs are looked up inside `day_dir`.
+    pub fn load(&self, day_dir: impl AsRef<Path>) -> std::io::Result<PuzzleInput> {
+        match self {
+            InputSource::Stdin => {
+                let mut data = Vec::new();
+                std::io::stdin()
+                    .read_to_end(&mut data)
+                    .map_err(|e| std::io::Error::new(e.kind(), format!("stdin: {}", e)))?;
+                Ok(PuzzleInput {
+                    data,
+                    meta: InputMeta::new("stdin"),
+                    origin: "stdin".to_string(),
+                })
+            }
+            InputSource::Named(name) => {
+                let path = day_dir.as_ref().join(format!("{}.txt", name));
+                let mut input = PuzzleInput::from_path(path)?;
+                input.meta.registered = true;
+                Ok(input)
+            }
+            InputSource::Path(path) => {
+                let mut input = PuzzleInput::from_path(path)?;
+                input.meta.registered = is_day_input(path, day_dir.as_ref());
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Directory of the crate for the given day.
+pub fn day_dir(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("aoc-2024-{}", day))
+}
+
+/// Whether the path resolves to an input file inside the directory of the day, like
+/// `aoc-2024-7/input.txt`, rather than a file of the same name somewhere else.
+fn is_day_input(path: &Path, day_dir: &Path) -> bool {
+    let (Ok(path), Ok(day_dir)) = (path.canonicalize(), day_dir.canonicalize()) else {
+        return false;
+    };
+    path.parent() == Some(day_dir.as_path()) && path.extension().is_some_and(|ext| ext == "txt")
+}
+
+/// Metadata of a puzzle input.
+///
+/// Some puzzles use parameters which are not part of the input itself, for example the grid
+/// dimensions of day 14. These can be specified inside a metadata file with the same name as the
+/// input file and the `.meta` extension. Each line of the file contains a `key = value` pair.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InputMeta {
+    /// Name of the input, which is the file stem for file inputs.
+    pub name: String,
+    /// Whether the input is one of the inputs inside the directory of the day, whose answers are
+    /// kept in the answers registry.
+    pub registered: bool,
+    /// Path of the metadata file the parameters were read from.
+    pub path: Option<PathBuf>,
+    pub params: HashMap<String, String>,
+}
+
+impl InputMeta {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            registered: false,
+            path: None,
+            params: HashMap::new(),
+        }
+    }
+
+    pub fn parse_params(&mut self, data: &str) {
+        for line in data.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                self.params
+                    .insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+    }
+
+    /// Parsed parameter value, or [None] if the parameter does not exist or can not be parsed.
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.params.get(key).and_then(|value| value.parse().ok())
+    }
+
+    /// Parsed parameter value, or the given default if the parameter does not exist. A value which
+    /// can not be parsed is an error naming the parameter and the metadata file.
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, ParseError> {
+        let Some(value) = self.params.get(key) else {
+            return Ok(default);
+        };
+        value.parse().map_err(|_| {
+            let file = match &self.path {
+                Some(path) => path.display().to_string(),
+                None => format!("{}.{}", self.name, META_EXTENSION),
+            };
+            ParseError::input(format!("invalid value {:?} for {} in {}", value, key, file))
+        })
+    }
+}
+
+/// Raw puzzle input together with its metadata.
+#[derive(Debug, Default, Clone)]
+pub struct PuzzleInput {
+    pub data: Vec<u8>,
+    pub meta: InputMeta,
+    /// Where the input was read from, used as the prefix of error messages.
+    pub origin: String,
+}
+
+impl PuzzleInput {
+    /// Reads the input file and its metadata file. Errors contain the path of the file.
+    pub fn from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let with_path =
+            |e: std::io::Error| std::io::Error::new(e.kind(), format!("{}: {}", path.display(), e));
+        let data = std::fs::read(path).map_err(with_path)?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut meta = InputMeta::new(&name);
+        let meta_path = path.with_extension(META_EXTENSION);
+        if meta_path.exists() {
+            meta.parse_params(&std::fs::read_to_string(&meta_path).map_err(with_path)?);
+            meta.path = Some(meta_path);
+        }
+        Ok(Self {
+            data,
+            meta,
+            origin: path.display().to_string(),
+        })
+    }
+
+    /// Whether this is the real puzzle input of the day, `input.txt` inside the directory of the
+    /// day.
+    pub fn is_default(&self) -> bool {
+        self.meta.registered && self.meta.name == DEFAULT_INPUT_NAME
+    }
+
+    /// Parses the input with the given parser. Exits the process with an error message pointing
+    /// at the invalid part of the input if parsing fails.
+    pub fn parse_or_exit<'a, T>(
+        &'a self,
+        parser: impl FnOnce(&'a [u8]) -> Result<T, ParseError>,
+    ) -> T {
+        match parser(&self.data) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("{}", e.report(&self.origin));
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Loads the input for the binary of a single day. The input is selected with the first command
+/// line argument or the [INPUT_ENV_VAR] environment variable, and named inputs are looked up inside
+/// `day_dir`. Exits the process if the input can not be loaded.
+pub fn load_input(day_dir: impl AsRef<Path>) -> PuzzleInput {
+    let explicit = std::env::args()
+        .nth(1)
+        .map(|arg| arg.parse::<InputSource>());
+    match explicit.transpose() {
+        Ok(explicit) => load_selected_input(day_dir, explicit),
+        Err(e) => {
+            eprintln!("invalid input: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Like [load_input], for binaries which parse their own command line arguments and pass the
+/// input source they were given, if any.
+pub fn load_selected_input(
+    day_dir: impl AsRef<Path>,
+    explicit: Option<InputSource>,
+) -> PuzzleInput {
+    let source = match InputSource::select(explicit) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("invalid input: {}", e);
+            std::process::exit(1);
+        }
+    };
+    match source.load(day_dir) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_input_source() {
+        assert_eq!("-".parse::<InputSource>().unwrap(), InputSource::Stdin);
+        assert_eq!(
+            "example1_wide".parse::<InputSource>().unwrap(),
+            InputSource::Named("example1_wide".to_string())
+        );
+        assert_eq!(
+            "my_input.txt".parse::<InputSource>().unwrap(),
+            InputSource::Path(PathBuf::from("my_input.txt"))
+        );
+        assert_eq!(
+            "inputs/day1".parse::<InputSource>().unwrap(),
+            InputSource::Path(PathBuf::from("inputs/day1"))
+        );
+        assert!("".parse::<InputSource>().is_err());
+    }
+
+    #[test]
+    fn parse_meta_params() {
+        let mut meta = InputMeta::new("example");
+        meta.parse_params("# Grid size\nx_dim = 11\ny_dim=7\n\n");
+        assert_eq!(meta.get::<usize>("x_dim"), Some(11));
+        assert_eq!(meta.get::<usize>("y_dim"), Some(7));
+        assert_eq!(meta.get_or::<usize>("z_dim", 3), Ok(3));
+        assert_eq!(meta.get_or::<usize>("x_dim", 3), Ok(11));
+
+        meta.parse_params("x_dim=1O1");
+        meta.path = Some(PathBuf::from("aoc-2024-14/example.meta"));
+        assert_eq!(
+            meta.get_or::<usize>("x_dim", 3).unwrap_err().to_string(),
+            "invalid value \"1O1\" for x_dim in aoc-2024-14/example.meta"
+        );
+    }
+
+    #[test]
+    fn registered_inputs() {
+        let day_dir = day_dir(7);
+        let named = InputSource::Named(DEFAULT_INPUT_NAME.to_string())
+            .load(&day_dir)
+            .unwrap();
+        assert!(named.is_default());
+        let path = InputSource::Path(day_dir.join("input.txt"))
+            .load(&day_dir)
+            .unwrap();
+        assert!(path.is_default());
+
+        // A file named like the real input of another day is not verified against its answers.
+        let other = InputSource::Path(super::day_dir(1).join("input.txt"))
+            .load(&day_dir)
+            .unwrap();
+        assert_eq!(other.meta.name, DEFAULT_INPUT_NAME);
+        assert!(!other.is_default());
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+mod answers;
+mod input;
+mod parse;
+
+pub use answers::*;
+pub use input::*;
+pub use parse::*;
+
+/// Puzzle part which should be solved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(value),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Answer of one puzzle part. Most answers are plain numbers, but some puzzles expect a
+/// comma-separated list or similar text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(u64),
+    Text(String),
+    /// The part has not been solved (yet).
+    Unsolved,
+    /// The solver could not solve the part for this input, with the reason.
+    Failed(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(num) => write!(f, "{}", num),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "unsolved"),
+            Answer::Failed(reason) => write!(f, "unsolved: {}", reason),
+        }
+    }
+}
+
+macro_rules! answer_from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(value: $ty) -> Self {
+                    Answer::Number(value as u64)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_unsigned!(u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// Common interface implemented by every day of the calendar.
+///
+/// The input is parsed once and then shared by both parts, so the parts must not rely on any
+/// state left behind by the other part.
+pub trait Solver {
+    /// Parsed puzzle input.
+    type Input;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError>;
+
+    /// Parses the input with its metadata. Only days which need parameters which are not part of
+    /// the input itself have to override this.
+    fn parse_with_meta(data: &[u8], _meta: &InputMeta) -> Result<Self::Input, ParseError> {
+        Self::parse(data)
+    }
+
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+/// Answer of one part and the time it took to solve the part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartSolution {
+    pub part: Part,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+/// Answers of all requested parts for one input, with the parse time measured separately from the
+/// time of each part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub parse_time: Duration,
+    pub parts: Vec<PartSolution>,
+}
+
+impl Solution {
+    pub fn answers(self) -> Vec<Answer> {
+        self.parts.into_iter().map(|part| part.answer).collect()
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|part| part.time).sum::<Duration>()
+    }
+}
+
+/// Formats a duration with a unit which keeps the number short, for example `1.23ms`.
+pub fn format_duration(time: Duration) -> String {
+    let micros = time.as_secs_f64() * 1e6;
+    if micros < 1000.0 {
+        format!("{:.1}µs", micros)
+    } else if micros < 1e6 {
+        format!("{:.2}ms", micros / 1e3)
+    } else {
+        format!("{:.2}s", micros / 1e6)
+    }
+}
+
+/// Parses the puzzle input once and solves all requested parts with solver `S`.
+pub fn solve<S: Solver>(input: &PuzzleInput, parts: &[Part]) -> Result<Vec<Answer>, ParseError> {
+    solve_timed::<S>(input, parts).map(Solution::answers)
+}
+
+/// Like [solve], but measures the time spent parsing and solving each part.
+pub fn solve_timed<S: Solver>(input: &PuzzleInput, parts: &[Part]) -> Result<Solution, ParseError> {
+    let start = Instant::now();
+    let input = S::parse_with_meta(&input.data, &input.meta)?;
+    let parse_time = start.elapsed();
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part1(&input),
+                Part::Two => S::part2(&input),
+            };
+            PartSolution {
+                part,
+                answer,
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok(Solution { parse_time, parts })
+}
@@ -0,0 +1,190 @@
+use std::{fmt::Display, str::FromStr};
+
+/// Error of an input parser, pointing at the position of the input which could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the error, or 0 if the error concerns the input as a whole.
+    pub line: usize,
+    /// 1-based column of the error, or 0 if the error concerns the whole line.
+    pub column: usize,
+    /// Description of what was expected or found.
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Error which can not be attributed to a position, for example missing elements.
+    pub fn input(message: impl Into<String>) -> Self {
+        Self::new(0, 0, message)
+    }
+
+    /// Error for an unexpected character, for example `unexpected 'x' in maze`.
+    pub fn unexpected(line: usize, column: usize, found: char, context: &str) -> Self {
+        Self::new(
+            line,
+            column,
+            format!("unexpected {:?} in {}", found, context),
+        )
+    }
+
+    /// Error for an element which does not occur in the input, for example `no 'S' in maze`.
+    pub fn missing(what: &str, context: &str) -> Self {
+        Self::input(format!("no {} in {}", what, context))
+    }
+
+    /// Formats the error prefixed with the origin of the input, like a compiler diagnostic:
+    /// `input.txt:12:7: unexpected 'x' in maze`.
+    pub fn report(&self, origin: impl Display) -> String {
+        if self.line == 0 {
+            format!("{}: {}", origin, self.message)
+        } else {
+            format!("{}:{}", origin, self)
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (0, _) => write!(f, "{}", self.message),
+            (line, 0) => write!(f, "{}: {}", line, self.message),
+            (line, column) => write!(f, "{}:{}: {}", line, column, self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Checks that the input is valid UTF-8. The error points at the first invalid byte.
+pub fn input_text(data: &[u8]) -> Result<&str, ParseError> {
+    std::str::from_utf8(data).map_err(|e| {
+        let valid = &data[..e.valid_up_to()];
+        let line = valid.iter().filter(|&&b| b == b'\n').count() + 1;
+        let line_start = valid
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |idx| idx + 1);
+        ParseError::new(line, valid.len() - line_start + 1, "invalid UTF-8")
+    })
+}
+
+/// Lines of the input together with their line numbers.
+pub fn input_lines(data: &[u8]) -> Result<impl Iterator<Item = Line<'_>>, ParseError> {
+    Ok(input_text(data)?
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| Line {
+            number: idx + 1,
+            text,
+        }))
+}
+
+/// One line of the input with its 1-based line number. Parts of the line which are passed to the
+/// helpers must be slices of [Line::text], so their column can be determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Self {
+        Self { number, text }
+    }
+
+    /// 1-based column at which `part` starts. Parts which are not slices of the line are placed
+    /// behind the end of the line.
+    pub fn column_of(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        if offset <= self.text.len() {
+            offset + 1
+        } else {
+            self.text.len() + 1
+        }
+    }
+
+    pub fn error(&self, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, column, message)
+    }
+
+    /// Error for a part of the line which is not what was expected.
+    pub fn expected(&self, part: &str, expected: &str) -> ParseError {
+        let message = if part.is_empty() {
+            format!("expected {}", expected)
+        } else {
+            format!("expected {}, found {:?}", expected, part)
+        };
+        self.error(self.column_of(part), message)
+    }
+
+    /// Error for an unexpected character at the given 0-based byte offset of the line.
+    pub fn unexpected(&self, offset: usize, context: &str) -> ParseError {
+        let found = self.text[offset..].chars().next().unwrap_or('\n');
+        ParseError::unexpected(self.number, offset + 1, found, context)
+    }
+
+    /// Parses a part of the line, for example a number.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.trim()
+            .parse()
+            .map_err(|_| self.expected(part.trim(), expected))
+    }
+
+    /// Splits the line at the first occurrence of the separator.
+    pub fn split_once(&self, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(separator)
+            .ok_or_else(|| self.error(0, format!("expected {:?}", separator)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_positions() {
+        let error = ParseError::unexpected(12, 7, 'x', "maze");
+        assert_eq!(error.to_string(), "12:7: unexpected 'x' in maze");
+        assert_eq!(
+            error.report("input.txt"),
+            "input.txt:12:7: unexpected 'x' in maze"
+        );
+        assert_eq!(
+            ParseError::missing("start 'S'", "maze").report("input.txt"),
+            "input.txt: no start 'S' in maze"
+        );
+        assert_eq!(ParseError::new(3, 0, "empty").to_string(), "3: empty");
+    }
+
+    #[test]
+    fn line_helpers() {
+        let lines: Vec<Line> = input_lines(b"3   4\n4 x\n").unwrap().collect();
+        assert_eq!(lines.len(), 2);
+        let mut fields = lines[1].text.split_whitespace();
+        assert_eq!(
+            lines[1].parse::<u32>(fields.next().unwrap(), "a number"),
+            Ok(4)
+        );
+        let error = lines[1]
+            .parse::<u32>(fields.next().unwrap(), "a number")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "2:3: expected a number, found \"x\"");
+        assert_eq!(lines[0].split_once("   "), Ok(("3", "4")));
+        assert!(lines[0].split_once(",").is_err());
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let error = input_text(b"ab\ncd\xffe").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
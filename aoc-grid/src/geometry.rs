@@ -0,0 +1,274 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Unsigned coordinate, used for positions on a map. For grids, `x` is the row index and `y` is
+/// the column index.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Copy, Clone, Ord, PartialOrd)]
+pub struct Coord2D {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// Signed coordinate, used for offsets and for positions which may leave the map.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Copy, Clone, Ord, PartialOrd)]
+pub struct ICoord2D {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Coord2D {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Applies a signed offset. Returns [None] if the result has negative components.
+    pub fn checked_offset(self, offset: ICoord2D) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(offset.x as isize)?,
+            y: self.y.checked_add_signed(offset.y as isize)?,
+        })
+    }
+
+    /// Applies a signed offset on a torus-shaped map with the given dimensions: leaving the map
+    /// on one side enters it again on the opposite side.
+    pub fn wrapping_offset(self, offset: ICoord2D, x_dim: usize, y_dim: usize) -> Self {
+        (ICoord2D::from(self) + offset).wrap(x_dim, y_dim)
+    }
+
+    /// Neighbour in the given direction. Returns [None] if the neighbour has negative
+    /// components.
+    pub fn checked_step(self, dir: Direction) -> Option<Self> {
+        self.checked_offset(dir.offset())
+    }
+
+    /// Neighbour in the given direction.
+    ///
+    /// Panics if the neighbour has negative components.
+    pub fn step(self, dir: Direction) -> Self {
+        self.checked_step(dir)
+            .unwrap_or_else(|| panic!("step {:?} from {:?} leaves the map", dir, self))
+    }
+
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl ICoord2D {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn has_negative_parts(&self) -> bool {
+        self.x < 0 || self.y < 0
+    }
+
+    /// Converts to an unsigned coordinate. Returns [None] if a component is negative.
+    pub fn unsigned(self) -> Option<Coord2D> {
+        Some(Coord2D {
+            x: usize::try_from(self.x).ok()?,
+            y: usize::try_from(self.y).ok()?,
+        })
+    }
+
+    /// Wraps the coordinate into a torus-shaped map with the given dimensions.
+    pub fn wrap(self, x_dim: usize, y_dim: usize) -> Coord2D {
+        Coord2D {
+            x: self.x.rem_euclid(x_dim as i64) as usize,
+            y: self.y.rem_euclid(y_dim as i64) as usize,
+        }
+    }
+
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl From<Coord2D> for ICoord2D {
+    fn from(coord: Coord2D) -> Self {
+        Self {
+            x: coord.x as i64,
+            y: coord.y as i64,
+        }
+    }
+}
+
+impl TryFrom<ICoord2D> for Coord2D {
+    type Error = ICoord2D;
+
+    fn try_from(coord: ICoord2D) -> Result<Self, Self::Error> {
+        coord.unsigned().ok_or(coord)
+    }
+}
+
+impl From<(usize, usize)> for Coord2D {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Coord2D> for (usize, usize) {
+    fn from(coord: Coord2D) -> Self {
+        (coord.x, coord.y)
+    }
+}
+
+macro_rules! coord_ops {
+    ($($ty:ty),*) => {
+        $(
+            impl Add for $ty {
+                type Output = Self;
+                fn add(self, rhs: Self) -> Self::Output {
+                    Self {
+                        x: self.x + rhs.x,
+                        y: self.y + rhs.y,
+                    }
+                }
+            }
+
+            impl Sub for $ty {
+                type Output = Self;
+                fn sub(self, rhs: Self) -> Self::Output {
+                    Self {
+                        x: self.x - rhs.x,
+                        y: self.y - rhs.y,
+                    }
+                }
+            }
+
+            impl AddAssign for $ty {
+                fn add_assign(&mut self, rhs: Self) {
+                    *self = *self + rhs;
+                }
+            }
+
+            impl SubAssign for $ty {
+                fn sub_assign(&mut self, rhs: Self) {
+                    *self = *self - rhs;
+                }
+            }
+        )*
+    };
+}
+
+coord_ops!(Coord2D, ICoord2D);
+
+impl Neg for ICoord2D {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl Mul<i64> for ICoord2D {
+    type Output = Self;
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+/// Direction on a map where north points to the first row.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Copy, Clone, Ord, PartialOrd)]
+pub enum Direction {
+    #[default]
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise starting in the north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// 90 degrees turn clockwise.
+    pub fn cw(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    /// 90 degrees turn counter-clockwise.
+    pub fn ccw(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// Offset of one step in this direction.
+    pub fn offset(self) -> ICoord2D {
+        match self {
+            Direction::North => ICoord2D::new(-1, 0),
+            Direction::East => ICoord2D::new(0, 1),
+            Direction::South => ICoord2D::new(1, 0),
+            Direction::West => ICoord2D::new(0, -1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.cw().ccw(), dir);
+            assert_eq!(dir.cw().cw(), dir.reverse());
+            assert_eq!(dir.reverse().offset(), -dir.offset());
+        }
+        assert_eq!(Direction::North.cw(), Direction::East);
+    }
+
+    #[test]
+    fn offsets() {
+        let origin = Coord2D::new(0, 0);
+        assert_eq!(origin.checked_step(Direction::North), None);
+        assert_eq!(origin.step(Direction::South), Coord2D::new(1, 0));
+        assert_eq!(
+            Coord2D::new(2, 3).checked_offset(ICoord2D::new(-2, 1)),
+            Some(Coord2D::new(0, 4))
+        );
+        assert_eq!(ICoord2D::new(-1, 2).unsigned(), None);
+        assert_eq!(Coord2D::new(1, 5).manhattan(Coord2D::new(4, 2)), 6);
+        assert_eq!(ICoord2D::new(-1, 5).manhattan(ICoord2D::new(2, -2)), 10);
+    }
+
+    #[test]
+    fn wrapping() {
+        let coord = Coord2D::new(2, 4);
+        assert_eq!(
+            coord.wrapping_offset(ICoord2D::new(2, -3), 11, 7),
+            Coord2D::new(4, 1)
+        );
+        assert_eq!(
+            coord.wrapping_offset(ICoord2D::new(-3, 3) * 2, 11, 7),
+            Coord2D::new(7, 3)
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use std::ops::{Index, IndexMut};
+
+use aoc_common::ParseError;
+
+mod geometry;
+
+pub use geometry::*;
+
+/// Offsets of the 8-connected neighbours, clockwise starting in the north.
+const OFFSETS_8: [ICoord2D; 8] = [
+    ICoord2D::new(-1, 0),
+    ICoord2D::new(-1, 1),
+    ICoord2D::new(0, 1),
+    ICoord2D::new(1, 1),
+    ICoord2D::new(1, 0),
+    ICoord2D::new(1, -1),
+    ICoord2D::new(0, -1),
+    ICoord2D::new(-1, -1),
+];
+
+/// Dense, row-major two-dimensional grid. Cells can be indexed with a [Coord2D] or with a
+/// `(row, column)` tuple.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Returns a copy of the grid with the outermost ring of cells removed. Most mazes are
+    /// surrounded by walls which do not need to be tracked.
+    pub fn without_border(&self) -> Self {
+        let rows = self.rows.saturating_sub(2);
+        let cols = self.cols.saturating_sub(2);
+        let cells = (0..rows)
+            .flat_map(|x| self.row(x + 1)[1..=cols].iter().cloned())
+            .collect();
+        Self { cells, rows, cols }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses a text grid, converting each byte with the given function. Parsing stops at the
+    /// first empty line, so a grid followed by other sections of the input can be parsed
+    /// directly.
+    ///
+    /// Bytes for which the function returns [None] are reported as unexpected characters in the
+    /// given context, for example `maze`. Rows of different lengths are rejected as well.
+    pub fn parse_with(
+        data: &[u8],
+        context: &str,
+        mut cell: impl FnMut(Coord2D, u8) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut rows = 0;
+        let mut cols = 0;
+        for line in data.split(|&b| b == b'\n') {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.is_empty() {
+                break;
+            }
+            if rows == 0 {
+                cols = line.len();
+            } else if line.len() != cols {
+                return Err(ParseError::new(
+                    rows + 1,
+                    line.len().min(cols) + 1,
+                    format!(
+                        "expected {} columns in {}, found {}",
+                        cols,
+                        context,
+                        line.len()
+                    ),
+                ));
+            }
+            for (y, &b) in line.iter().enumerate() {
+                match cell(Coord2D::new(rows, y), b) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        return Err(ParseError::unexpected(rows + 1, y + 1, b as char, context))
+                    }
+                }
+            }
+            rows += 1;
+        }
+        Ok(Self { cells, rows, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, pos: Coord2D) -> bool {
+        pos.x < self.rows && pos.y < self.cols
+    }
+
+    pub fn get(&self, pos: Coord2D) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.x * self.cols + pos.y])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Coord2D) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.x * self.cols + pos.y])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, x: usize) -> &[T] {
+        &self.cells[x * self.cols..(x + 1) * self.cols]
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Coord2D> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |x| (0..cols).map(move |y| Coord2D::new(x, y)))
+    }
+
+    /// All cells together with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord2D, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position which is offset from the given one, if it is inside the grid.
+    pub fn offset(&self, pos: Coord2D, offset: ICoord2D) -> Option<Coord2D> {
+        pos.checked_offset(offset)
+            .filter(|&next| self.contains(next))
+    }
+
+    /// Neighbour in the given direction, if it is inside the grid.
+    pub fn step(&self, pos: Coord2D, dir: Direction) -> Option<Coord2D> {
+        self.offset(pos, dir.offset())
+    }
+
+    /// 4-connected neighbours inside the grid in the order north, east, south, west.
+    pub fn neighbors4(&self, pos: Coord2D) -> impl Iterator<Item = Coord2D> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// 8-connected neighbours inside the grid, clockwise starting in the north.
+    pub fn neighbors8(&self, pos: Coord2D) -> impl Iterator<Item = Coord2D> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Renders the grid back to text, with one line per row.
+    pub fn render_with(&self, mut glyph: impl FnMut(Coord2D, &T) -> char) -> String {
+        let mut text = String::with_capacity(self.rows * (self.cols + 1));
+        for (pos, cell) in self.iter() {
+            text.push(glyph(pos, cell));
+            if pos.y == self.cols - 1 {
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a text grid of raw glyphs, rejecting all glyphs which are not in `glyphs`. See
+    /// [Grid::parse_with].
+    pub fn parse(data: &[u8], context: &str, glyphs: &[u8]) -> Result<Self, ParseError> {
+        Self::parse_with(data, context, |_, b| glyphs.contains(&b).then_some(b))
+    }
+
+    /// Position of the only occurrence of a glyph which must be part of the grid, for example
+    /// the start of a maze.
+    pub fn find_required(&self, glyph: u8, context: &str) -> Result<Coord2D, ParseError> {
+        self.find(glyph)
+            .ok_or_else(|| ParseError::missing(&format!("{:?}", glyph as char), context))
+    }
+
+    /// Position of the first occurrence of the glyph in row-major order.
+    pub fn find(&self, glyph: u8) -> Option<Coord2D> {
+        self.find_all(glyph).next()
+    }
+
+    pub fn find_all(&self, glyph: u8) -> impl Iterator<Item = Coord2D> + '_ {
+        self.iter()
+            .filter(move |(_, &cell)| cell == glyph)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn render(&self) -> String {
+        self.render_with(|_, &b| b as char)
+    }
+}
+
+impl<T, P: Into<Coord2D>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let pos = pos.into();
+        assert!(self.contains(pos), "position {:?} outside of grid", pos);
+        &self.cells[pos.x * self.cols + pos.y]
+    }
+}
+
+impl<T, P: Into<Coord2D>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+        let pos = pos.into();
+        assert!(self.contains(pos), "position {:?} outside of grid", pos);
+        &mut self.cells[pos.x * self.cols + pos.y]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &[u8] = b"#####\n#S..#\n#.#E#\n#####\n\n<>^v\n";
+    const MAZE_GLYPHS: &[u8] = b"#.SE";
+
+    #[test]
+    fn parse_find_and_render() {
+        let grid = Grid::parse(MAZE, "maze", MAZE_GLYPHS).unwrap();
+        assert_eq!(grid.rows(), 4);
+        assert_eq!(grid.cols(), 5);
+        assert_eq!(grid.find(b'S'), Some(Coord2D::new(1, 1)));
+        assert_eq!(grid.find(b'E'), Some(Coord2D::new(2, 3)));
+        assert_eq!(grid.find(b'x'), None);
+        assert_eq!(
+            grid.find_required(b'x', "maze").unwrap_err().to_string(),
+            "no 'x' in maze"
+        );
+        assert_eq!(grid.find_all(b'.').count(), 3);
+        assert_eq!(grid[(2, 2)], b'#');
+        assert_eq!(grid.render(), "#####\n#S..#\n#.#E#\n#####\n");
+        let inner = grid.without_border();
+        assert_eq!(inner.render(), "S..\n.#E\n");
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, 0u8);
+        let neighbors4: Vec<(usize, usize)> = grid
+            .neighbors4(Coord2D::new(0, 0))
+            .map(Into::into)
+            .collect();
+        assert_eq!(neighbors4, [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors4(Coord2D::new(1, 1)).count(), 4);
+        let neighbors8: Vec<(usize, usize)> = grid
+            .neighbors8(Coord2D::new(0, 2))
+            .map(Into::into)
+            .collect();
+        assert_eq!(neighbors8, [(1, 2), (1, 1), (0, 1)]);
+        assert_eq!(grid.neighbors8(Coord2D::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Grid::parse(b"###\n##\n", "maze", MAZE_GLYPHS).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "2:3: expected 3 columns in maze, found 2"
+        );
+        let error = Grid::parse(b"###\n#x#\n", "maze", MAZE_GLYPHS).unwrap_err();
+        assert_eq!(error.to_string(), "2:2: unexpected 'x' in maze");
+    }
+}
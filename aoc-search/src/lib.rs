@@ -0,0 +1,343 @@
+//! Graph searches over implicit graphs. A graph is described by a `neighbors` function which
+//! returns the states reachable from a given state, together with the cost of the step for the
+//! weighted searches. All searches are iterative, so they also work for very long paths.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+const NO_PARENT: usize = usize::MAX;
+
+/// Cheapest path found by a search, including the start and the goal state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: usize,
+    pub states: Vec<S>,
+}
+
+/// Assigns an index to every visited state. The searches work with these indexes, so states
+/// only need to be hashed once and do not need to implement [Ord] for the priority queue.
+struct States<S> {
+    states: Vec<S>,
+    indexes: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> States<S> {
+    fn new() -> Self {
+        Self {
+            states: Vec::new(),
+            indexes: HashMap::new(),
+        }
+    }
+
+    /// Returns the index of the state and whether the state was seen for the first time.
+    fn intern(&mut self, state: S) -> (usize, bool) {
+        if let Some(&idx) = self.indexes.get(&state) {
+            return (idx, false);
+        }
+        let idx = self.states.len();
+        self.states.push(state.clone());
+        self.indexes.insert(state, idx);
+        (idx, true)
+    }
+
+    fn path(&self, parents: &[usize], goal: usize, cost: usize) -> Path<S> {
+        let mut states = Vec::new();
+        let mut idx = goal;
+        while idx != NO_PARENT {
+            states.push(self.states[idx].clone());
+            idx = parents[idx];
+        }
+        states.reverse();
+        Path { cost, states }
+    }
+}
+
+/// Breadth-first search for graphs where every step has the same cost. The cost of the returned
+/// path is its number of steps.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut states = States::new();
+    let mut parents = vec![NO_PARENT];
+    let mut steps = vec![0];
+    let mut queue = VecDeque::from([states.intern(start).0]);
+    while let Some(idx) = queue.pop_front() {
+        let current = states.states[idx].clone();
+        if is_goal(&current) {
+            return Some(states.path(&parents, idx, steps[idx]));
+        }
+        for next in neighbors(&current) {
+            let (next_idx, new) = states.intern(next);
+            if new {
+                parents.push(idx);
+                steps.push(steps[idx] + 1);
+                queue.push_back(next_idx);
+            }
+        }
+    }
+    None
+}
+
+/// Number of steps from the start to every reachable state.
+pub fn bfs_distances<S, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((current, steps)) = queue.pop_front() {
+        for next in neighbors(&current) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Dijkstra search for the cheapest path to a goal state.
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(start, neighbors, |_| 0, is_goal)
+}
+
+/// A* search for the cheapest path to a goal state. The heuristic estimates the remaining cost
+/// to the goal and must never overestimate it, otherwise the returned path might not be the
+/// cheapest one.
+pub fn astar<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut states = States::new();
+    let mut parents = vec![NO_PARENT];
+    let mut costs = vec![0];
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((heuristic(&start), 0, states.intern(start).0)));
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        // Stale queue entry, the state was reached more cheaply in the meantime.
+        if cost > costs[idx] {
+            continue;
+        }
+        let current = states.states[idx].clone();
+        if is_goal(&current) {
+            return Some(states.path(&parents, idx, cost));
+        }
+        for (next, step_cost) in neighbors(&current) {
+            let next_cost = cost + step_cost;
+            let estimate = next_cost + heuristic(&next);
+            let (next_idx, new) = states.intern(next);
+            if new {
+                parents.push(NO_PARENT);
+                costs.push(usize::MAX);
+            }
+            if next_cost < costs[next_idx] {
+                costs[next_idx] = next_cost;
+                parents[next_idx] = idx;
+                heap.push(Reverse((estimate, next_cost, next_idx)));
+            }
+        }
+    }
+    None
+}
+
+/// All cheapest paths from the start to the goal states, stored as a DAG of predecessors.
+#[derive(Debug, Clone)]
+pub struct AllPaths<S> {
+    pub cost: usize,
+    states: Vec<S>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<S: Clone + Eq + Hash> AllPaths<S> {
+    /// Goal states which can be reached with the lowest cost.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&idx| &self.states[idx])
+    }
+
+    /// All states which are part of at least one cheapest path, each listed once.
+    pub fn states_on_paths(&self) -> Vec<&S> {
+        let mut visited = HashSet::new();
+        let mut stack = self.goals.clone();
+        while let Some(idx) = stack.pop() {
+            if visited.insert(idx) {
+                stack.extend(&self.predecessors[idx]);
+            }
+        }
+        visited.into_iter().map(|idx| &self.states[idx]).collect()
+    }
+
+    /// Enumerates all cheapest paths from the start to a goal state. The number of paths can
+    /// grow exponentially with the size of the graph, so [Self::states_on_paths] should be
+    /// preferred if only the visited states are of interest.
+    pub fn paths(&self) -> Vec<Vec<S>> {
+        let mut paths = Vec::new();
+        // The paths are built backwards from the goals, one predecessor at a time.
+        let mut stack: Vec<Vec<usize>> = self.goals.iter().map(|&goal| vec![goal]).collect();
+        while let Some(path) = stack.pop() {
+            let last = *path.last().unwrap();
+            if self.predecessors[last].is_empty() {
+                paths.push(
+                    path.iter()
+                        .rev()
+                        .map(|&idx| self.states[idx].clone())
+                        .collect(),
+                );
+                continue;
+            }
+            for &pred in &self.predecessors[last] {
+                let mut next_path = path.clone();
+                next_path.push(pred);
+                stack.push(next_path);
+            }
+        }
+        paths
+    }
+}
+
+/// Dijkstra search which keeps track of all cheapest paths to the goal states instead of only
+/// one of them.
+pub fn dijkstra_all<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<AllPaths<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut states = States::new();
+    let mut predecessors = vec![Vec::new()];
+    let mut costs = vec![0];
+    let mut goals = Vec::new();
+    let mut best_cost = None;
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((0, states.intern(start).0)));
+    while let Some(Reverse((cost, idx))) = heap.pop() {
+        if cost > costs[idx] {
+            continue;
+        }
+        if best_cost.is_some_and(|best_cost| cost > best_cost) {
+            break;
+        }
+        let current = states.states[idx].clone();
+        if is_goal(&current) {
+            best_cost = Some(cost);
+            goals.push(idx);
+            continue;
+        }
+        for (next, step_cost) in neighbors(&current) {
+            let next_cost = cost + step_cost;
+            let (next_idx, new) = states.intern(next);
+            if new {
+                predecessors.push(Vec::new());
+                costs.push(usize::MAX);
+            }
+            if next_cost < costs[next_idx] {
+                costs[next_idx] = next_cost;
+                predecessors[next_idx] = vec![idx];
+                heap.push(Reverse((next_cost, next_idx)));
+            } else if next_cost == costs[next_idx] {
+                predecessors[next_idx].push(idx);
+            }
+        }
+    }
+    best_cost.map(|cost| AllPaths {
+        cost,
+        states: states.states,
+        predecessors,
+        goals,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Open 4x4 grid with a wall segment, states are `(row, column)`.
+    fn grid_neighbors(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+            .into_iter()
+            .filter(|&(x, y)| (0..4).contains(&x) && (0..4).contains(&y))
+            .filter(|&pos| pos != (1, 1) && pos != (1, 2) && pos != (1, 3))
+            .collect()
+    }
+
+    #[test]
+    fn bfs_shortest_path() {
+        let path = bfs((0, 3), grid_neighbors, |&pos| pos == (2, 3)).unwrap();
+        assert_eq!(path.cost, 8);
+        assert_eq!(path.states.len(), 9);
+        assert_eq!(path.states.first(), Some(&(0, 3)));
+        assert_eq!(path.states.last(), Some(&(2, 3)));
+        assert!(bfs((0, 0), grid_neighbors, |&pos| pos == (9, 9)).is_none());
+        let distances = bfs_distances((0, 0), grid_neighbors);
+        assert_eq!(distances.len(), 13);
+        assert_eq!(distances[&(3, 3)], 6);
+    }
+
+    fn weighted_neighbors(&node: &u8) -> Vec<(u8, usize)> {
+        match node {
+            0 => vec![(1, 1), (2, 4)],
+            1 => vec![(2, 2), (3, 6)],
+            2 => vec![(3, 3)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_and_astar() {
+        let path = dijkstra(0, weighted_neighbors, |&node| node == 3).unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.states, [0, 1, 2, 3]);
+        let path = astar(
+            0,
+            weighted_neighbors,
+            |&node| 3 - node as usize,
+            |&node| node == 3,
+        );
+        assert_eq!(path.unwrap().states, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn all_cheapest_paths() {
+        // Diamond with two equally expensive paths and one more expensive path.
+        let neighbors = |&node: &u8| match node {
+            0 => vec![(1, 1), (2, 1), (3, 5)],
+            1 | 2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        let all_paths = dijkstra_all(0, neighbors, |&node| node == 3).unwrap();
+        assert_eq!(all_paths.cost, 2);
+        assert_eq!(all_paths.goals().collect::<Vec<_>>(), [&3]);
+        let mut states = all_paths.states_on_paths();
+        states.sort();
+        assert_eq!(states, [&0, &1, &2, &3]);
+        let mut paths = all_paths.paths();
+        paths.sort();
+        assert_eq!(paths, [vec![0, 1, 3], vec![0, 2, 3]]);
+    }
+}
@@ -0,0 +1,167 @@
+//! Local history of timed runs, used to spot performance regressions across commits. Every run of
+//! the `time` command appends one JSON line per day to the history file.
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::report::DayReport;
+
+/// The history file is kept in the repository root and ignored by git.
+pub fn history_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("aoc-history.jsonl")
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// Short hash of the commit the run was made on, or `unknown` outside of a git checkout.
+    pub commit: String,
+    /// The working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub report: DayReport,
+}
+
+impl HistoryEntry {
+    /// Commit label shown in the tables, with a `+` appended for a dirty working tree.
+    pub fn label(&self) -> String {
+        if self.dirty {
+            format!("{}+", self.commit)
+        } else {
+            self.commit.clone()
+        }
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Commit and dirty state of the working tree.
+pub fn current_commit() -> (String, bool) {
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => {
+            let dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+            (commit, dirty)
+        }
+        None => ("unknown".to_string(), false),
+    }
+}
+
+/// Creates the history entries for the reports of one run.
+pub fn entries(reports: &[DayReport]) -> Vec<HistoryEntry> {
+    let (commit, dirty) = current_commit();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    reports
+        .iter()
+        .map(|report| HistoryEntry {
+            commit: commit.clone(),
+            dirty,
+            timestamp,
+            report: report.clone(),
+        })
+        .collect()
+}
+
+/// Reads all entries of the history file. A missing file is an empty history, and lines which
+/// can not be parsed are skipped.
+pub fn load(path: &Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(path)
+        .map(|history| {
+            history
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn append(path: &Path, entries: &[HistoryEntry]) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for entry in entries {
+        let line = serde_json::to_string(entry).expect("history entries are serializable");
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Most recent run of the same day and input with another [HistoryEntry::label] than `label`.
+/// Repeated runs on a clean commit are compared against the previous commit, while runs with
+/// uncommitted changes are compared against the commit they are based on.
+pub fn baseline<'a>(
+    history: &'a [HistoryEntry],
+    label: &str,
+    report: &DayReport,
+) -> Option<&'a HistoryEntry> {
+    history.iter().rev().find(|entry| {
+        entry.label() != label
+            && entry.report.day == report.day
+            && entry.report.input == report.input
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(commit: &str, day: u8, parse_ns: u64) -> HistoryEntry {
+        HistoryEntry {
+            commit: commit.to_string(),
+            dirty: false,
+            timestamp: 0,
+            report: DayReport {
+                day,
+                input: "input".to_string(),
+                parse_ns,
+                parts: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn baseline_is_latest_other_commit() {
+        let history = vec![
+            entry("aaaaaaa", 1, 10),
+            entry("bbbbbbb", 1, 20),
+            entry("bbbbbbb", 2, 30),
+            entry("ccccccc", 1, 40),
+        ];
+        let report = &entry("ccccccc", 1, 50).report;
+        assert_eq!(baseline(&history, "ccccccc", report), Some(&history[1]));
+        assert_eq!(baseline(&history, "ccccccc+", report), Some(&history[3]));
+        assert_eq!(baseline(&history, "ccccccc", &entry("", 3, 0).report), None);
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert!(load(&path).is_empty());
+        let entries = vec![entry("aaaaaaa", 1, 10), entry("aaaaaaa", 2, 20)];
+        append(&path, &entries).unwrap();
+        append(&path, &entries[..1]).unwrap();
+        let history = load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[..2], entries[..]);
+    }
+}
@@ -0,0 +1,350 @@
+use std::panic::AssertUnwindSafe;
+
+use aoc_common::{
+    day_dir, format_duration, known_answers, solve_timed, Answer, InputSource, Part, PuzzleInput,
+    Solution, Verdict, DEFAULT_INPUT_NAME,
+};
+use clap::{Parser, Subcommand};
+use report::{format_change, format_nanos, DayReport, Format};
+
+mod history;
+mod report;
+
+#[derive(Parser, Debug)]
+#[command(about = "Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve one day of the calendar.
+    Run {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Part to solve. Both parts are solved if this is omitted.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input: a path, `-` for stdin or the name of an input of the day like
+        /// `example0`. Defaults to the `AOC_INPUT` environment variable or `input`.
+        #[arg(short, long)]
+        input: Option<InputSource>,
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Time parsing and both parts of all days and record the times in the history file.
+    Time {
+        /// Only time this day.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Name of the input of each day.
+        #[arg(short, long, default_value = DEFAULT_INPUT_NAME)]
+        input: String,
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+        /// Do not append the times to the history file.
+        #[arg(long)]
+        no_history: bool,
+    },
+    /// Show the recorded times of earlier `time` runs, oldest first.
+    History {
+        /// Only show this day.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+    },
+    /// Solve all days for all inputs with known answers and compare the results.
+    Verify {
+        /// Only verify this day.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+    },
+}
+
+/// Solves the requested parts of one day and measures the parse time and the time of each part.
+/// Parse errors are prefixed with the origin of the input, for example
+/// `input.txt:12:7: unexpected 'x' in maze`.
+fn solve_day(day: u8, input: &PuzzleInput, parts: &[Part]) -> Result<Solution, String> {
+    let result = match day {
+        1 => solve_timed::<aoc_2024_1::Day1>(input, parts),
+        2 => solve_timed::<aoc_2024_2::Day2>(input, parts),
+        3 => solve_timed::<aoc_2024_3::Day3>(input, parts),
+        4 => solve_timed::<aoc_2024_4::Day4>(input, parts),
+        5 => solve_timed::<aoc_2024_5::Day5>(input, parts),
+        6 => solve_timed::<aoc_2024_6::Day6>(input, parts),
+        7 => solve_timed::<aoc_2024_7::Day7>(input, parts),
+        8 => solve_timed::<aoc_2024_8::Day8>(input, parts),
+        9 => solve_timed::<aoc_2024_9::Day9>(input, parts),
+        10 => solve_timed::<aoc_2024_10::Day10>(input, parts),
+        11 => solve_timed::<aoc_2024_11::Day11>(input, parts),
+        12 => solve_timed::<aoc_2024_12::Day12>(input, parts),
+        13 => solve_timed::<aoc_2024_13::Day13>(input, parts),
+        14 => solve_timed::<aoc_2024_14::Day14>(input, parts),
+        15 => solve_timed::<aoc_2024_15::Day15>(input, parts),
+        16 => solve_timed::<aoc_2024_16::Day16>(input, parts),
+        17 => solve_timed::<aoc_2024_17::Day17>(input, parts),
+        18 => solve_timed::<aoc_2024_18::Day18>(input, parts),
+        19 => solve_timed::<aoc_2024_19::Day19>(input, parts),
+        20 => solve_timed::<aoc_2024_20::Day20>(input, parts),
+        21 => solve_timed::<aoc_2024_21::Day21>(input, parts),
+        22 => solve_timed::<aoc_2024_22::Day22>(input, parts),
+        23 => solve_timed::<aoc_2024_23::Day23>(input, parts),
+        24 => solve_timed::<aoc_2024_24::Day24>(input, parts),
+        25 => solve_timed::<aoc_2024_25::Day25>(input, parts),
+        _ => return Err(format!("no solution for day {}", day)),
+    };
+    result.map_err(|e| e.report(&input.origin))
+}
+
+fn run(day: u8, part: Option<u8>, input: Option<InputSource>, format: Format) {
+    let parts = match part {
+        Some(part) => vec![Part::try_from(part).unwrap()],
+        None => vec![Part::One, Part::Two],
+    };
+    let source = match InputSource::select(input) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("invalid input: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let input = match source.load(day_dir(day)) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    match solve_day(day, &input, &parts) {
+        Ok(solution) => {
+            let failed = solution
+                .parts
+                .iter()
+                .any(|part| matches!(part.answer, Answer::Failed(_)));
+            print_solution(day, &input, solution, format);
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_solution(day: u8, input: &PuzzleInput, solution: Solution, format: Format) {
+    match format {
+        Format::Table => {
+            println!(
+                "Day {} parsed in {}",
+                day,
+                format_duration(solution.parse_time)
+            );
+            for part in solution.parts {
+                println!(
+                    "Day {} part {}: {} ({})",
+                    day,
+                    part.part,
+                    part.answer,
+                    format_duration(part.time)
+                );
+            }
+        }
+        Format::Json => report::print_json(&[DayReport::new(day, &input.meta.name, &solution)]),
+    }
+}
+
+#[derive(Debug, Default)]
+struct VerifyStats {
+    passed: usize,
+    failed: usize,
+    unknown: usize,
+}
+
+/// Verifies both parts of one day for one input. Failures of a solver, for example panics, are
+/// reported as failures and do not abort the verification.
+fn verify_input(day: u8, input_name: &str, stats: &mut VerifyStats) {
+    let answers = known_answers();
+    let input = match InputSource::Named(input_name.to_string()).load(day_dir(day)) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("Day {:>2} {}: FAIL ({})", day, input_name, e);
+            stats.failed += 2;
+            return;
+        }
+    };
+    for part in [Part::One, Part::Two] {
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            solve_day(day, &input, &[part]).map(|solution| solution.answers().remove(0))
+        }));
+        let answer = match result {
+            Ok(Ok(answer)) => answer,
+            Ok(Err(e)) => {
+                println!("Day {:>2} part {} {}: FAIL ({})", day, part, input_name, e);
+                stats.failed += 1;
+                continue;
+            }
+            Err(_) => {
+                println!(
+                    "Day {:>2} part {} {}: FAIL (panicked)",
+                    day, part, input_name
+                );
+                stats.failed += 1;
+                continue;
+            }
+        };
+        let verdict = answers.check(day, part, input_name, &answer);
+        println!(
+            "Day {:>2} part {} {}: {} ({})",
+            day, part, input_name, verdict, answer
+        );
+        match verdict {
+            Verdict::Pass => stats.passed += 1,
+            Verdict::Fail { .. } => stats.failed += 1,
+            Verdict::Unknown => stats.unknown += 1,
+        }
+    }
+}
+
+fn verify(day: Option<u8>) {
+    let days = match day {
+        Some(day) => vec![day],
+        None => (1..=25).collect(),
+    };
+    let mut stats = VerifyStats::default();
+    for day in days {
+        let mut inputs = known_answers().inputs(day);
+        if !inputs.contains(&DEFAULT_INPUT_NAME) {
+            inputs.push(DEFAULT_INPUT_NAME);
+        }
+        for input_name in inputs {
+            verify_input(day, input_name, &mut stats);
+        }
+    }
+    println!(
+        "{} passed, {} failed, {} unknown",
+        stats.passed, stats.failed, stats.unknown
+    );
+    if stats.failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Times one day. Failures are printed and reported as [None], so the other days are still timed.
+fn time_day(day: u8, input_name: &str) -> Option<DayReport> {
+    let input = match InputSource::Named(input_name.to_string()).load(day_dir(day)) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Day {:>2} {}: {}", day, input_name, e);
+            return None;
+        }
+    };
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        solve_day(day, &input, &[Part::One, Part::Two])
+    }));
+    match result {
+        Ok(Ok(solution)) => Some(DayReport::new(day, input_name, &solution)),
+        Ok(Err(e)) => {
+            eprintln!("Day {:>2} {}: {}", day, input_name, e);
+            None
+        }
+        Err(_) => {
+            eprintln!("Day {:>2} {}: panicked", day, input_name);
+            None
+        }
+    }
+}
+
+fn time(day: Option<u8>, input_name: &str, format: Format, no_history: bool) {
+    let days = match day {
+        Some(day) => vec![day],
+        None => (1..=25).collect(),
+    };
+    let mut failed = false;
+    let mut reports = Vec::new();
+    for day in days {
+        match time_day(day, input_name) {
+            Some(report) => reports.push(report),
+            None => failed = true,
+        }
+    }
+    let path = history::history_path();
+    let past = history::load(&path);
+    let entries = history::entries(&reports);
+    match format {
+        Format::Table => report::print_table(&reports, |report| {
+            let label = entries.first().map_or(String::new(), |entry| entry.label());
+            history::baseline(&past, &label, report).map_or("-".to_string(), |baseline| {
+                format_change(report.total_ns(), baseline.report.total_ns())
+            })
+        }),
+        Format::Json => report::print_json(&reports),
+    }
+    if !no_history {
+        if let Err(e) = history::append(&path, &entries) {
+            eprintln!("failed to write {}: {}", path.display(), e);
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn show_history(day: Option<u8>) {
+    let history = history::load(&history::history_path());
+    let mut runs: Vec<(u8, &str)> = history
+        .iter()
+        .filter(|entry| day.is_none_or(|day| day == entry.report.day))
+        .map(|entry| (entry.report.day, entry.report.input.as_str()))
+        .collect();
+    runs.sort();
+    runs.dedup();
+    for (day, input) in runs {
+        println!("Day {} {}", day, input);
+        let mut previous_total = None;
+        for entry in history
+            .iter()
+            .filter(|entry| entry.report.day == day && entry.report.input == input)
+        {
+            let report = &entry.report;
+            let part_time = |part| {
+                report
+                    .part(part)
+                    .map_or("-".to_string(), |part| format_nanos(part.time_ns))
+            };
+            println!(
+                "    {:<9} {:>10} {:>10} {:>10} {:>10} {:>8}",
+                entry.label(),
+                format_nanos(report.parse_ns),
+                part_time(1),
+                part_time(2),
+                format_nanos(report.total_ns()),
+                previous_total.map_or("-".to_string(), |previous| {
+                    format_change(report.total_ns(), previous)
+                })
+            );
+            previous_total = Some(report.total_ns());
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+        } => run(day, part, input, format),
+        Command::Time {
+            day,
+            input,
+            format,
+            no_history,
+        } => time(day, &input, format, no_history),
+        Command::History { day } => show_history(day),
+        Command::Verify { day } => verify(day),
+    }
+}
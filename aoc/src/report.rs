@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use aoc_common::{format_duration, Solution};
+use serde::{Deserialize, Serialize};
+
+/// Output format of the solved days.
+#[derive(clap::ValueEnum, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+}
+
+/// Answer and time of one part. Times are stored in nanoseconds to keep the JSON output and the
+/// history file free of unit conversions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: String,
+    pub time_ns: u64,
+}
+
+/// Results of one day for one input.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: u8,
+    pub input: String,
+    pub parse_ns: u64,
+    pub parts: Vec<PartReport>,
+}
+
+fn nanos(time: Duration) -> u64 {
+    time.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+impl DayReport {
+    pub fn new(day: u8, input: &str, solution: &Solution) -> Self {
+        Self {
+            day,
+            input: input.to_string(),
+            parse_ns: nanos(solution.parse_time),
+            parts: solution
+                .parts
+                .iter()
+                .map(|part| PartReport {
+                    part: part.part.into(),
+                    answer: part.answer.to_string(),
+                    time_ns: nanos(part.time),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartReport> {
+        self.parts.iter().find(|report| report.part == part)
+    }
+
+    pub fn total_ns(&self) -> u64 {
+        self.parse_ns + self.parts.iter().map(|part| part.time_ns).sum::<u64>()
+    }
+}
+
+pub fn format_nanos(nanos: u64) -> String {
+    format_duration(Duration::from_nanos(nanos))
+}
+
+/// Relative change of the total time against an earlier run, for example `+12%`.
+pub fn format_change(total_ns: u64, baseline_ns: u64) -> String {
+    if baseline_ns == 0 {
+        return "-".to_string();
+    }
+    let change = (total_ns as f64 / baseline_ns as f64 - 1.0) * 100.0;
+    format!("{:+.0}%", change)
+}
+
+pub fn print_json(reports: &[DayReport]) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(reports).expect("reports are serializable")
+    );
+}
+
+/// Prints one row per report with the parse, part and total times. `change` returns the content
+/// of the last column for a report.
+pub fn print_table(reports: &[DayReport], change: impl Fn(&DayReport) -> String) {
+    println!(
+        "{:>3} {:<12} {:>10} {:>10} {:>10} {:>10} {:>8}",
+        "Day", "Input", "Parse", "Part 1", "Part 2", "Total", "Change"
+    );
+    for report in reports {
+        let part_time = |part| {
+            report
+                .part(part)
+                .map_or("-".to_string(), |part| format_nanos(part.time_ns))
+        };
+        println!(
+            "{:>3} {:<12} {:>10} {:>10} {:>10} {:>10} {:>8}",
+            report.day,
+            report.input,
+            format_nanos(report.parse_ns),
+            part_time(1),
+            part_time(2),
+            format_nanos(report.total_ns()),
+            change(report)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{Answer, Part, PartSolution};
+
+    use super::*;
+
+    #[test]
+    fn report_from_solution() {
+        let solution = Solution {
+            parse_time: Duration::from_micros(5),
+            parts: vec![
+                PartSolution {
+                    part: Part::One,
+                    answer: Answer::Number(42),
+                    time: Duration::from_millis(2),
+                },
+                PartSolution {
+                    part: Part::Two,
+                    answer: Answer::Text("1,2,3".to_string()),
+                    time: Duration::from_millis(3),
+                },
+            ],
+        };
+        let report = DayReport::new(17, "example", &solution);
+        assert_eq!(report.parse_ns, 5_000);
+        assert_eq!(report.part(2).unwrap().answer, "1,2,3");
+        assert_eq!(report.total_ns(), 5_005_000);
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<DayReport>(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn change() {
+        assert_eq!(format_change(120, 100), "+20%");
+        assert_eq!(format_change(50, 100), "-50%");
+        assert_eq!(format_change(50, 0), "-");
+    }
+}